@@ -0,0 +1,216 @@
+/// Bot runner - plays many headless games with the built-in bot
+///
+/// Usage: `bot [GAMES] [MAX_TURNS] [FIRST_SEED]`
+///
+/// Reports how the games ended, how deep the bot got and what killed it, to
+/// surface crashes and balance problems. A panic only ends its own game; the
+/// run exits with an error if any game crashed.
+///
+/// Game N is played on seed FIRST_SEED + N - 1 (random seeds if FIRST_SEED
+/// is left out). Each game's seed is printed, so `--seed` can play it again.
+
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Mutex;
+use bevy::prelude::*;
+use rust_roguelike::{constants::*, headless_app, GameState, TurnState};
+use rust_roguelike::components::{Player, Name};
+use rust_roguelike::resources::{ActionSource, Depth, GameRng};
+use rust_roguelike::systems::EntityKilled;
+
+/// Where and why the last panic happened
+static LAST_PANIC: Mutex<Option<String>> = Mutex::new(None);
+
+enum Outcome {
+    Victory,
+    Died { killer: String },
+    TimedOut,
+    Stuck,
+    Crashed(String),
+}
+
+struct GameReport {
+    seed: u64,
+    outcome: Outcome,
+    depth: u32,
+    turns: u32,
+}
+
+/// Monster kind without its number ("Goblin #3" -> "Goblin")
+fn kind_of(name: &str) -> &str {
+    name.split(" #").next().unwrap_or(name)
+}
+
+/// Play one game from `seed` to the end, a timeout or a stall
+fn play_game(seed: u64, max_turns: u32) -> GameReport {
+    let mut app = headless_app(ActionSource::Bot);
+    app.insert_resource(GameRng::new(seed));
+    let mut report = GameReport { seed, outcome: Outcome::TimedOut, depth: 1, turns: 0 };
+    let mut killer = None;
+    let mut was_player_turn = true;
+    let mut stalled_frames = 0;
+
+    loop {
+        app.update();
+        let world = app.world_mut();
+        report.depth = world.resource::<Depth>().0;
+
+        // Note who killed the player before their body is gone
+        let player = world.query_filtered::<Entity, With<Player>>().get_single(world).ok();
+        let player_killed = world
+            .resource::<Events<EntityKilled>>()
+            .iter_current_update_events()
+            .find(|event| Some(event.entity) == player)
+            .copied();
+        if let Some(event) = player_killed {
+            killer = Some(
+                event
+                    .killer
+                    .and_then(|entity| world.get::<Name>(entity))
+                    .map(|name| kind_of(&name.0).to_string())
+                    .unwrap_or_else(|| "trap".to_string()),
+            );
+        }
+
+        match world.resource::<State<GameState>>().get() {
+            GameState::Victory => {
+                report.outcome = Outcome::Victory;
+                return report;
+            }
+            GameState::GameOver => {
+                report.outcome = Outcome::Died { killer: killer.unwrap_or_else(|| "unknown".to_string()) };
+                return report;
+            }
+            _ => {}
+        }
+
+        let player_turn = *world.resource::<State<TurnState>>().get() == TurnState::PlayerTurn;
+        if player_turn && !was_player_turn {
+            report.turns += 1;
+            stalled_frames = 0;
+        } else {
+            stalled_frames += 1;
+        }
+        was_player_turn = player_turn;
+
+        if report.turns >= max_turns {
+            return report;
+        }
+        if stalled_frames >= BOT_STALL_FRAMES {
+            report.outcome = Outcome::Stuck;
+            return report;
+        }
+    }
+}
+
+fn parse_arg<T: std::str::FromStr>(arg: Option<String>, default: T) -> T {
+    match arg {
+        None => default,
+        Some(text) => text.parse().unwrap_or_else(|_| {
+            eprintln!("Usage: bot [GAMES] [MAX_TURNS] [FIRST_SEED]");
+            std::process::exit(2);
+        }),
+    }
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let games: usize = parse_arg(args.next(), BOT_DEFAULT_GAMES);
+    let max_turns: u32 = parse_arg(args.next(), BOT_MAX_TURNS);
+    let first_seed: u64 = parse_arg(args.next(), rand::random());
+
+    panic::set_hook(Box::new(|info| {
+        let location = info.location().map(|l| format!(" at {}:{}", l.file(), l.line())).unwrap_or_default();
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panic".to_string());
+        *LAST_PANIC.lock().unwrap_or_else(|err| err.into_inner()) = Some(format!("{}{}", message, location));
+    }));
+
+    let mut reports = Vec::with_capacity(games);
+    for game in 1..=games {
+        let seed = first_seed.wrapping_add(game as u64 - 1);
+        eprint!("\rGame {}/{}", game, games);
+        let report = panic::catch_unwind(AssertUnwindSafe(|| play_game(seed, max_turns))).unwrap_or_else(|_| {
+            let message = LAST_PANIC
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .take()
+                .unwrap_or_else(|| "panic".to_string());
+            GameReport { seed, outcome: Outcome::Crashed(message), depth: 0, turns: 0 }
+        });
+        reports.push(report);
+    }
+    eprintln!();
+
+    // Summary
+    let mut victories = 0;
+    let mut timed_out = 0;
+    let mut stuck = 0;
+    let mut killers: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut deaths_by_depth: BTreeMap<u32, usize> = BTreeMap::new();
+    let mut deepest: BTreeMap<u32, usize> = BTreeMap::new();
+    let mut crashes = Vec::new();
+
+    for (game, report) in reports.iter().enumerate() {
+        match &report.outcome {
+            Outcome::Victory => victories += 1,
+            Outcome::Died { killer } => {
+                *killers.entry(killer).or_default() += 1;
+                *deaths_by_depth.entry(report.depth).or_default() += 1;
+            }
+            Outcome::TimedOut => timed_out += 1,
+            Outcome::Stuck => stuck += 1,
+            Outcome::Crashed(message) => crashes.push((game + 1, report.seed, message)),
+        }
+        if !matches!(report.outcome, Outcome::Crashed(_)) {
+            *deepest.entry(report.depth).or_default() += 1;
+        }
+    }
+
+    let finished: Vec<&GameReport> = reports.iter().filter(|r| !matches!(r.outcome, Outcome::Crashed(_))).collect();
+    let average_turns = finished.iter().map(|r| r.turns as f32).sum::<f32>() / finished.len().max(1) as f32;
+    let deaths: usize = killers.values().sum();
+
+    println!("Played {} games (at most {} turns each)", games, max_turns);
+    for (game, report) in reports.iter().enumerate() {
+        let outcome = match &report.outcome {
+            Outcome::Victory => "victory".to_string(),
+            Outcome::Died { killer } => format!("killed by {} on depth {}", killer, report.depth),
+            Outcome::TimedOut => format!("timed out on depth {}", report.depth),
+            Outcome::Stuck => format!("stuck on depth {}", report.depth),
+            Outcome::Crashed(_) => "crashed".to_string(),
+        };
+        println!("  game {} (seed {}): {} after {} turns", game + 1, report.seed, outcome, report.turns);
+    }
+    println!("  victories: {}", victories);
+    println!("  deaths:    {}", deaths);
+    println!("  timed out: {}", timed_out);
+    println!("  stuck:     {}", stuck);
+    println!("  crashes:   {}", crashes.len());
+    println!("Average turns per game: {:.0}", average_turns);
+    println!("Deepest level reached:");
+    for (depth, count) in &deepest {
+        println!("  depth {}: {}", depth, count);
+    }
+    println!("Deaths by depth:");
+    for (depth, count) in &deaths_by_depth {
+        println!("  depth {}: {}", depth, count);
+    }
+    println!("Killed by:");
+    let mut killers: Vec<_> = killers.into_iter().collect();
+    killers.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    for (killer, count) in killers {
+        println!("  {}: {}", killer, count);
+    }
+    if !crashes.is_empty() {
+        println!("Crashes:");
+        for (game, seed, message) in &crashes {
+            println!("  game {} (seed {}): {}", game, seed, message);
+        }
+        std::process::exit(1);
+    }
+}
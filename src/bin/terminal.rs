@@ -0,0 +1,52 @@
+/// Terminal entry point - plays the game in a text UI, without a window
+///
+/// Runs the same game rules as the windowed build, without its renderer; the
+/// terminal frontend draws the map as glyphs and feeds terminal key presses
+/// to the game.
+/// Quit with Ctrl-C or Ctrl-Q. Takes the same `--bot`, `--replay FILE`,
+/// `--record FILE` and `--seed N` arguments as the windowed build.
+
+use std::time::Duration;
+use bevy::prelude::*;
+use bevy::app::ScheduleRunnerPlugin;
+use bevy::input::InputPlugin;
+use bevy::state::app::StatesPlugin as BevyStatesPlugin;
+use rust_roguelike::{
+    constants::*, StatesPlugin, GameRulesPlugin, GameState, TerminalFrontendPlugin, apply_action_source_args,
+};
+
+fn main() {
+    let mut app = App::new();
+    app
+        // Headless Bevy: no window, renderer or audio
+        .add_plugins((
+            MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(Duration::from_secs_f64(1.0 / TERMINAL_FPS))),
+            InputPlugin,
+            BevyStatesPlugin,
+        ))
+        // The game rules only; the terminal frontend draws and reads keys
+        .add_plugins((StatesPlugin, GameRulesPlugin))
+        .add_systems(Startup, setup);
+
+    // Keyboard, replay or bot (checked before the terminal switches to raw mode)
+    if let Err(err) = apply_action_source_args(&mut app, std::env::args().skip(1)) {
+        eprintln!("{}", err);
+        std::process::exit(2);
+    }
+
+    let terminal = match ratatui::try_init() {
+        Ok(terminal) => terminal,
+        Err(err) => {
+            ratatui::restore();
+            eprintln!("Can't use this terminal: {}", err);
+            std::process::exit(1);
+        }
+    };
+
+    app.add_plugins(TerminalFrontendPlugin::new(terminal)).run();
+}
+
+/// Start the game straight away
+fn setup(mut next_state: ResMut<NextState<GameState>>) {
+    next_state.set(GameState::Playing);
+}
@@ -1,19 +1,73 @@
 /// Actor components - Player and entity markers
 
 use bevy::prelude::*;
+use crate::constants::Z_LAYER_CHARACTERS;
 
 /// Marker component for the player entity
 #[derive(Component)]
 pub struct Player;
 
-/// Component for rendering entities as colored squares
+/// Marker for non-actor entities (traps, items, doors) that stay on the map
+/// display once seen, dimmed while out of view
+#[derive(Component)]
+pub struct Memorable;
+
+/// Component for rendering entities as colored squares, as a tileset
+/// sprite when a tileset is loaded, or as a CP437 glyph in glyph mode
 #[derive(Component)]
 pub struct Renderable {
     pub color: Color,
+    /// Index into the tileset (None = always a colored square)
+    pub tile: Option<usize>,
+    /// Character drawn in glyph mode
+    pub glyph: char,
+    /// Color behind the glyph in glyph mode (transparent for none)
+    pub background: Color,
+    /// Sprite size as a fraction of a tile
+    pub size: f32,
+    /// Draw order (one of the Z_LAYER_* constants)
+    pub layer: f32,
 }
 
 impl Renderable {
     pub fn new(color: Color) -> Self {
-        Self { color }
+        Self {
+            color,
+            tile: None,
+            glyph: '?',
+            background: Color::NONE,
+            size: 1.0,
+            layer: Z_LAYER_CHARACTERS,
+        }
+    }
+
+    /// Set the tileset index (builder style)
+    pub fn with_tile(mut self, tile: usize) -> Self {
+        self.tile = Some(tile);
+        self
+    }
+
+    /// Set the glyph (builder style)
+    pub fn with_glyph(mut self, glyph: char) -> Self {
+        self.glyph = glyph;
+        self
+    }
+
+    /// Set the glyph background (builder style)
+    pub fn with_background(mut self, background: Color) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Set the sprite size as a fraction of a tile (builder style)
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the draw layer (builder style)
+    pub fn with_layer(mut self, layer: f32) -> Self {
+        self.layer = layer;
+        self
     }
 }
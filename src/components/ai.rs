@@ -0,0 +1,90 @@
+/// AI components - monster behaviour states, packs and stealth
+
+use bevy::prelude::*;
+use crate::resources::map::Position;
+
+// ============================================================================
+// AI STATE COMPONENT
+// ============================================================================
+
+/// What a monster is currently doing
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AiState {
+    /// Dormant until it notices the player
+    Asleep,
+
+    /// Drifting around its post
+    Wandering,
+
+    /// Heading for a noise it heard
+    Investigating { target: Position },
+
+    /// Chasing the player, heading for where it last saw them
+    Hunting { last_known: Position },
+
+    /// Running away from the player at low health
+    Fleeing,
+
+    /// Walking back to its post after losing the player
+    Returning,
+}
+
+impl AiState {
+    /// Short label for the debug overlay
+    pub fn label(&self) -> &'static str {
+        match self {
+            AiState::Asleep => "asleep",
+            AiState::Wandering => "wandering",
+            AiState::Investigating { .. } => "investigating",
+            AiState::Hunting { .. } => "hunting",
+            AiState::Fleeing => "fleeing",
+            AiState::Returning => "returning",
+        }
+    }
+
+    /// Whether the monster is actively tracking the player
+    pub fn is_aware(&self) -> bool {
+        matches!(self, AiState::Hunting { .. } | AiState::Fleeing)
+    }
+}
+
+/// The actor a monster is currently fighting, picked from the hostile
+/// actors it can see
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AiTarget(pub Option<Entity>);
+
+// ============================================================================
+// STEALTH COMPONENT
+// ============================================================================
+
+/// Marker for an actor moving in sneak mode (quieter but slower)
+#[derive(Component, Debug)]
+pub struct Sneaking;
+
+// ============================================================================
+// GUARD POST COMPONENT
+// ============================================================================
+
+/// The tile a monster wanders around and returns to
+#[derive(Component, Debug, Clone, Copy)]
+pub struct GuardPost(pub Position);
+
+// ============================================================================
+// PACK COMPONENTS
+// ============================================================================
+
+/// Membership of a monster pack; the leader points at itself
+#[derive(Component, Debug, Clone, Copy)]
+pub struct PackMember {
+    pub leader: Entity,
+}
+
+/// Marker for the monster leading its pack
+#[derive(Component, Debug)]
+pub struct PackLeader;
+
+/// Marker for a monster whose pack broke when its leader died
+///
+/// Routed monsters flee whenever they see the player.
+#[derive(Component, Debug)]
+pub struct Routed;
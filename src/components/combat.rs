@@ -1,6 +1,10 @@
 /// Combat-related components for health, stats, and entity identification
 
 use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::constants::{
+    BASE_ACCURACY, BASE_EVASION, RESISTANT_DAMAGE_MULTIPLIER, VULNERABLE_DAMAGE_MULTIPLIER,
+};
 
 // ============================================================================
 // HEALTH COMPONENT
@@ -43,20 +47,188 @@ impl Health {
     }
 }
 
+// ============================================================================
+// REGENERATION COMPONENT
+// ============================================================================
+
+/// Natural healing: one hit point every `turns_per_point` turns
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Regeneration {
+    pub turns_per_point: u32,
+    pub progress: u32,
+}
+
+impl Regeneration {
+    pub fn new(turns_per_point: u32) -> Self {
+        Self {
+            turns_per_point,
+            progress: 0,
+        }
+    }
+
+    /// Advance one turn; true when a point of health is due
+    pub fn tick(&mut self) -> bool {
+        self.progress += 1;
+        if self.progress >= self.turns_per_point {
+            self.progress = 0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
 // ============================================================================
 // COMBAT STATS COMPONENT
 // ============================================================================
 
 /// Combat statistics for attack and defense
+///
+/// `accuracy` is compared against the defender's `evasion` to decide hit,
+/// glancing and critical chances (see `hit_distribution`).
 #[derive(Component, Debug, Clone, Copy)]
 pub struct CombatStats {
     pub power: i32,
     pub defense: i32,
+    pub accuracy: i32,
+    pub evasion: i32,
 }
 
 impl CombatStats {
     pub fn new(power: i32, defense: i32) -> Self {
-        Self { power, defense }
+        Self {
+            power,
+            defense,
+            accuracy: BASE_ACCURACY,
+            evasion: BASE_EVASION,
+        }
+    }
+
+    /// Set accuracy (builder style)
+    pub fn with_accuracy(mut self, accuracy: i32) -> Self {
+        self.accuracy = accuracy;
+        self
+    }
+
+    /// Set evasion (builder style)
+    pub fn with_evasion(mut self, evasion: i32) -> Self {
+        self.evasion = evasion;
+        self
+    }
+}
+
+// ============================================================================
+// DAMAGE TYPE
+// ============================================================================
+
+/// Kind of damage dealt by an attack or effect
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DamageType {
+    #[default]
+    Physical,
+    Fire,
+    Cold,
+    Poison,
+    Lightning,
+}
+
+impl DamageType {
+    /// Lowercase name for log messages
+    pub fn name(&self) -> &'static str {
+        match self {
+            DamageType::Physical => "physical",
+            DamageType::Fire => "fire",
+            DamageType::Cold => "cold",
+            DamageType::Poison => "poison",
+            DamageType::Lightning => "lightning",
+        }
+    }
+}
+
+// ============================================================================
+// RESISTANCES COMPONENT
+// ============================================================================
+
+/// How strongly an entity reacts to a damage type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resistance {
+    /// Takes no damage
+    Immune,
+    /// Takes reduced damage
+    Resistant,
+    /// Takes increased damage
+    Vulnerable,
+}
+
+impl Resistance {
+    /// Damage multiplier applied by this resistance
+    pub fn multiplier(&self) -> f32 {
+        match self {
+            Resistance::Immune => 0.0,
+            Resistance::Resistant => RESISTANT_DAMAGE_MULTIPLIER,
+            Resistance::Vulnerable => VULNERABLE_DAMAGE_MULTIPLIER,
+        }
+    }
+}
+
+/// Per-entity resistances, vulnerabilities and immunities
+///
+/// Damage types without an entry are taken at full strength.
+#[derive(Component, Debug, Clone, Default)]
+pub struct Resistances {
+    pub entries: HashMap<DamageType, Resistance>,
+}
+
+impl Resistances {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a resistance entry (builder style)
+    pub fn with(mut self, damage_type: DamageType, resistance: Resistance) -> Self {
+        self.entries.insert(damage_type, resistance);
+        self
+    }
+
+    /// Get the resistance to a damage type, if any
+    pub fn get(&self, damage_type: DamageType) -> Option<Resistance> {
+        self.entries.get(&damage_type).copied()
+    }
+
+    /// Scale incoming damage; returns the final amount and the resistance applied
+    ///
+    /// Non-immune targets always take at least 1 damage.
+    pub fn apply(&self, amount: i32, damage_type: DamageType) -> (i32, Option<Resistance>) {
+        match self.get(damage_type) {
+            Some(Resistance::Immune) => (0, Some(Resistance::Immune)),
+            Some(resistance) => {
+                let scaled = (amount as f32 * resistance.multiplier()).round() as i32;
+                (scaled.max(1), Some(resistance))
+            }
+            None => (amount, None),
+        }
+    }
+}
+
+// ============================================================================
+// WEAPON COMPONENT
+// ============================================================================
+
+/// The weapon an entity attacks with
+///
+/// Entities without a Weapon deal physical damage.
+#[derive(Component, Debug, Clone)]
+pub struct Weapon {
+    pub name: String,
+    pub damage_type: DamageType,
+}
+
+impl Weapon {
+    pub fn new(name: impl Into<String>, damage_type: DamageType) -> Self {
+        Self {
+            name: name.into(),
+            damage_type,
+        }
     }
 }
 
@@ -64,7 +236,9 @@ impl CombatStats {
 // ENEMY MARKER COMPONENT
 // ============================================================================
 
-/// Marker component for enemy entities
+/// Marker component for AI-controlled monsters
+///
+/// Who fights whom is decided by `Faction`, not by this marker.
 #[derive(Component, Debug)]
 pub struct Enemy;
 
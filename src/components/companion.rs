@@ -0,0 +1,52 @@
+/// Companion components - pets and allies fighting for the player
+
+use bevy::prelude::*;
+use crate::resources::map::Position;
+
+// ============================================================================
+// COMPANION COMPONENT
+// ============================================================================
+
+/// Marks an ally controlled by the companion AI rather than the monster AI
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Companion {
+    pub command: CompanionCommand,
+}
+
+impl Companion {
+    pub fn new() -> Self {
+        Self {
+            command: CompanionCommand::Follow,
+        }
+    }
+}
+
+impl Default for Companion {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Standing order given by the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompanionCommand {
+    /// Stay near the player, fighting whatever the player fights or is attacked by
+    Follow,
+
+    /// Hold a position, only fighting what comes adjacent
+    Stay { at: Position },
+
+    /// Seek out any hostile in view
+    Attack,
+}
+
+impl CompanionCommand {
+    /// How the order reads in the combat log
+    pub fn describe(&self) -> &'static str {
+        match self {
+            CompanionCommand::Follow => "follows you",
+            CompanionCommand::Stay { .. } => "stays put",
+            CompanionCommand::Attack => "goes on the attack",
+        }
+    }
+}
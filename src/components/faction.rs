@@ -0,0 +1,55 @@
+/// Faction components - who fights whom
+
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+// ============================================================================
+// FACTION COMPONENT
+// ============================================================================
+
+/// The side an actor belongs to
+///
+/// How factions treat each other is configured in `FactionTable`.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Faction {
+    Player,
+    Goblins,
+    Undead,
+    /// Dungeon creatures that mind their own business
+    Wildlife,
+}
+
+impl Faction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Faction::Player => "player",
+            Faction::Goblins => "goblins",
+            Faction::Undead => "undead",
+            Faction::Wildlife => "wildlife",
+        }
+    }
+}
+
+/// How one faction treats another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reaction {
+    Hostile,
+    Neutral,
+    Friendly,
+}
+
+// ============================================================================
+// GRUDGES COMPONENT
+// ============================================================================
+
+/// Actors that attacked this one and are now its enemies, whatever their faction
+#[derive(Component, Debug, Clone, Default)]
+pub struct Grudges {
+    pub against: HashSet<Entity>,
+}
+
+impl Grudges {
+    pub fn holds_against(&self, entity: Entity) -> bool {
+        self.against.contains(&entity)
+    }
+}
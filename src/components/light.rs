@@ -0,0 +1,22 @@
+/// Light components - entities that light up the tiles around them
+
+use bevy::prelude::*;
+
+/// Emits light over the tiles it can see, fading out towards `radius`
+#[derive(Component, Debug, Clone, Copy)]
+pub struct LightSource {
+    pub radius: i32,
+    pub color: Color,
+    /// Light level at the source (1.0 = fully lit)
+    pub intensity: f32,
+}
+
+impl LightSource {
+    pub fn new(radius: i32, color: Color, intensity: f32) -> Self {
+        Self {
+            radius,
+            color,
+            intensity,
+        }
+    }
+}
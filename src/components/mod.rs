@@ -5,9 +5,25 @@
 pub mod actor;
 pub mod viewshed;
 pub mod combat;
+pub mod trap;
+pub mod ai;
+pub mod monster;
+pub mod faction;
+pub mod companion;
+pub mod light;
 
-pub use actor::{Player, Renderable};
+pub use actor::{Player, Renderable, Memorable};
 pub use viewshed::Viewshed;
-pub use combat::{Health, CombatStats, Enemy, Name};
+pub use combat::{
+    Health, Regeneration, CombatStats, DamageType, Resistance, Resistances, Weapon, Enemy, Name,
+};
+pub use trap::{Trap, TrapKind, Concealed};
+pub use ai::{AiState, AiTarget, GuardPost, Sneaking, PackMember, PackLeader, Routed};
+pub use monster::{
+    MonsterBehaviour, Spell, SpellKind, KnownSpell, Spellbook, SummonedBy, Boss, BossPhase,
+};
+pub use faction::{Faction, Reaction, Grudges};
+pub use companion::{Companion, CompanionCommand};
+pub use light::LightSource;
 // Re-export Position from resources for convenience
 pub use crate::resources::map::Position;
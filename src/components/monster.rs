@@ -0,0 +1,158 @@
+/// Monster components - behaviour archetypes, spellcasting and bosses
+
+use bevy::prelude::*;
+use crate::components::combat::DamageType;
+
+// ============================================================================
+// BEHAVIOUR COMPONENT
+// ============================================================================
+
+/// How a monster fights once it is hunting the player
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonsterBehaviour {
+    /// Walks up and hits things
+    Melee,
+
+    /// Keeps its distance and shoots along clear lines
+    Archer {
+        range: i32,
+        preferred_distance: i32,
+    },
+
+    /// Casts spells from its spellbook, otherwise fights in melee
+    Caster,
+
+    /// Keeps its distance and summons minions from its spellbook
+    Summoner {
+        preferred_distance: i32,
+    },
+}
+
+// ============================================================================
+// SPELLS
+// ============================================================================
+
+/// Effect of a monster spell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpellKind {
+    /// Ranged attack of the given damage type along a clear line
+    Bolt { damage_type: DamageType },
+
+    /// Restore health to a wounded ally in view
+    HealAlly { amount: i32 },
+
+    /// Teleport a short distance away from the player
+    Blink,
+
+    /// Call a minion (by template id) into a free adjacent tile
+    Summon { minion: &'static str, max_minions: usize },
+
+    /// Attack of the given damage type against every hostile within `radius`
+    Nova { damage_type: DamageType, radius: i32 },
+}
+
+/// A spell as configured on a monster template
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Spell {
+    pub name: &'static str,
+    pub kind: SpellKind,
+    pub range: i32,
+    /// Enemy turns before the spell can be cast again
+    pub cooldown: u32,
+}
+
+/// A spell together with its remaining cooldown
+#[derive(Debug, Clone, Copy)]
+pub struct KnownSpell {
+    pub spell: Spell,
+    pub cooldown_remaining: u32,
+}
+
+/// Spells a monster can cast
+#[derive(Component, Debug, Clone, Default)]
+pub struct Spellbook {
+    pub spells: Vec<KnownSpell>,
+}
+
+impl Spellbook {
+    pub fn new(spells: &[Spell]) -> Self {
+        Self {
+            spells: spells
+                .iter()
+                .map(|spell| KnownSpell { spell: *spell, cooldown_remaining: 0 })
+                .collect(),
+        }
+    }
+
+    /// Count down every cooldown by one turn
+    pub fn tick(&mut self) {
+        for known in self.spells.iter_mut() {
+            known.cooldown_remaining = known.cooldown_remaining.saturating_sub(1);
+        }
+    }
+
+    /// Spells ready to cast this turn
+    pub fn ready(&self) -> impl Iterator<Item = (usize, &Spell)> {
+        self.spells
+            .iter()
+            .enumerate()
+            .filter(|(_, known)| known.cooldown_remaining == 0)
+            .map(|(idx, known)| (idx, &known.spell))
+    }
+
+    /// Put a spell on cooldown after casting it
+    pub fn start_cooldown(&mut self, idx: usize) {
+        if let Some(known) = self.spells.get_mut(idx) {
+            known.cooldown_remaining = known.spell.cooldown;
+        }
+    }
+}
+
+// ============================================================================
+// SUMMONING
+// ============================================================================
+
+/// Marks a minion and the monster that summoned it
+#[derive(Component, Debug, Clone, Copy)]
+pub struct SummonedBy(pub Entity);
+
+// ============================================================================
+// BOSSES
+// ============================================================================
+
+/// A stage of a boss fight, entered once health drops below a threshold
+#[derive(Debug, Clone, Copy)]
+pub struct BossPhase {
+    /// Health fraction (0.0-1.0) below which this phase starts
+    pub below_health: f32,
+    pub announcement: &'static str,
+    /// Replaces the boss's spellbook
+    pub spells: &'static [Spell],
+    pub power_bonus: i32,
+}
+
+/// A unique monster whose behaviour changes as it is worn down
+///
+/// Bosses never flee, and killing one on the final depth wins the game.
+#[derive(Component, Debug, Clone)]
+pub struct Boss {
+    pub phases: &'static [BossPhase],
+    /// Number of phases entered so far
+    pub phases_entered: usize,
+}
+
+impl Boss {
+    pub fn new(phases: &'static [BossPhase]) -> Self {
+        Self {
+            phases,
+            phases_entered: 0,
+        }
+    }
+
+    /// The next phase to enter at the given health fraction, if any
+    pub fn next_phase(&self, health_fraction: f32) -> Option<&'static BossPhase> {
+        self.phases
+            .get(self.phases_entered)
+            .filter(|phase| health_fraction < phase.below_health)
+    }
+}
@@ -0,0 +1,94 @@
+/// Trap components - hidden hazards placed during level generation
+
+use bevy::prelude::*;
+use crate::constants::{
+    COLOR_TRAP_DART, COLOR_TRAP_TELEPORT, COLOR_TRAP_ALARM, COLOR_TRAP_PIT,
+    TILE_TRAP_DART, TILE_TRAP_TELEPORT, TILE_TRAP_ALARM, TILE_TRAP_PIT,
+};
+
+// ============================================================================
+// TRAP KIND
+// ============================================================================
+
+/// The effect a trap has when triggered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrapKind {
+    /// Fires a poisoned dart at whoever steps on it
+    Dart,
+
+    /// Teleports the victim to a random walkable tile
+    Teleport,
+
+    /// Sounds an alarm audible across the level
+    Alarm,
+
+    /// Drops the victim into a shallow pit
+    Pit,
+}
+
+impl TrapKind {
+    /// All trap kinds, used for random selection during level generation
+    pub const ALL: [TrapKind; 4] = [
+        TrapKind::Dart,
+        TrapKind::Teleport,
+        TrapKind::Alarm,
+        TrapKind::Pit,
+    ];
+
+    /// Display name for log messages
+    pub fn name(&self) -> &'static str {
+        match self {
+            TrapKind::Dart => "dart trap",
+            TrapKind::Teleport => "teleport trap",
+            TrapKind::Alarm => "alarm trap",
+            TrapKind::Pit => "pit trap",
+        }
+    }
+
+    /// Color used when the trap has been revealed
+    pub fn color(&self) -> Color {
+        match self {
+            TrapKind::Dart => COLOR_TRAP_DART,
+            TrapKind::Teleport => COLOR_TRAP_TELEPORT,
+            TrapKind::Alarm => COLOR_TRAP_ALARM,
+            TrapKind::Pit => COLOR_TRAP_PIT,
+        }
+    }
+
+    /// Glyph drawn for the trap in glyph mode
+    pub fn glyph(&self) -> char {
+        '^'
+    }
+
+    /// Tileset index
+    pub fn tile(&self) -> usize {
+        match self {
+            TrapKind::Dart => TILE_TRAP_DART,
+            TrapKind::Teleport => TILE_TRAP_TELEPORT,
+            TrapKind::Alarm => TILE_TRAP_ALARM,
+            TrapKind::Pit => TILE_TRAP_PIT,
+        }
+    }
+}
+
+// ============================================================================
+// TRAP COMPONENTS
+// ============================================================================
+
+/// A trap occupying a single tile
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Trap {
+    pub kind: TrapKind,
+}
+
+impl Trap {
+    pub fn new(kind: TrapKind) -> Self {
+        Self { kind }
+    }
+}
+
+/// Marker for traps the player has not detected yet
+///
+/// Concealed entities are never revealed by the FOV system.
+#[derive(Component, Debug)]
+pub struct Concealed;
@@ -19,31 +19,126 @@ pub const CAMERA_FOLLOW_SPEED: f32 = 5.0;
 // Game balance
 pub const PLAYER_STARTING_HEALTH: i32 = 100;
 pub const PLAYER_STARTING_ACTION_POINTS: i32 = 1;
+pub const PLAYER_REGEN_TURNS: u32 = 4;          // Turns per hit point of natural healing
 pub const MOVEMENT_ACTION_COST: i32 = 1;
 pub const FOV_RADIUS: i32 = 8;
 
+// Lighting settings
+pub const MIN_VISIBLE_LIGHT: f32 = 0.15;       // Light level needed to see a tile
+pub const AMBIENT_LIGHT_BY_DEPTH: [f32; 4] = [0.3, 0.2, 0.1, 0.0]; // Deeper levels use the last value
+pub const PLAYER_LANTERN_RADIUS: i32 = 5;
+pub const TORCH_LIGHT_RADIUS: i32 = 6;
+pub const FUNGUS_LIGHT_RADIUS: i32 = 3;
+pub const TORCH_COUNT: usize = 5;
+pub const FUNGUS_COUNT: usize = 4;
+
 // Combat settings
 pub const ATTACK_ACTION_COST: i32 = 1;
-pub const BASE_HIT_CHANCE: u32 = 75;
+pub const BASE_HIT_CHANCE: i32 = 75;
 pub const DAMAGE_VARIANCE: i32 = 2;
+pub const BASE_ACCURACY: i32 = 5;
+pub const BASE_EVASION: i32 = 5;
+pub const HIT_CHANCE_PER_POINT: i32 = 5;       // Accuracy vs evasion, per point of difference
+pub const MIN_HIT_CHANCE: i32 = 5;
+pub const MAX_HIT_CHANCE: i32 = 95;
+pub const CRITICAL_HIT_CHANCE: i32 = 5;        // Plus 1 per point of accuracy over evasion
+pub const CRITICAL_HIT_MULTIPLIER: f32 = 2.0;
+pub const GLANCING_BLOW_CHANCE: i32 = 10;      // Plus 2 per point of evasion over accuracy
+pub const GLANCING_BLOW_MULTIPLIER: f32 = 0.5;
+pub const RESISTANT_DAMAGE_MULTIPLIER: f32 = 0.5;
+pub const VULNERABLE_DAMAGE_MULTIPLIER: f32 = 1.5;
 
 // Player combat stats
 pub const PLAYER_ATTACK_POWER: i32 = 10;
 pub const PLAYER_DEFENSE: i32 = 2;
+pub const PLAYER_ACCURACY: i32 = 7;
+pub const PLAYER_EVASION: i32 = 6;
 
 // Enemy combat stats
 pub const ENEMY_STARTING_HEALTH: i32 = 30;
 pub const ENEMY_ATTACK_POWER: i32 = 8;
 pub const ENEMY_DEFENSE: i32 = 1;
+pub const ENEMY_ACCURACY: i32 = 5;
+pub const ENEMY_EVASION: i32 = 4;
 pub const ENEMY_FOV_RADIUS: i32 = 6;
 pub const ENEMY_MIN_COUNT: usize = 3;
 pub const ENEMY_MAX_COUNT: usize = 5;
 
+// Monster AI settings
+pub const AI_WAKE_CHANCE: u32 = 35;            // Per turn, while the player is in view
+pub const AI_FLEE_HEALTH_THRESHOLD: f32 = 0.25;
+pub const AI_WANDER_RADIUS: i32 = 4;
+pub const AI_WANDER_MOVE_CHANCE: u32 = 50;
+pub const AI_START_ASLEEP_CHANCE: u32 = 50;
+pub const AI_HEAL_ALLY_THRESHOLD: f32 = 0.5;
+pub const BLINK_MIN_DISTANCE: i32 = 3;
+
+// Dungeon settings
+pub const FINAL_DEPTH: u32 = 5;                // Home of the boss arena
+
+// Companion settings
+pub const PET_STARTING_HEALTH: i32 = 24;
+pub const PET_ATTACK_POWER: i32 = 6;
+pub const PET_DEFENSE: i32 = 0;
+pub const PET_ACCURACY: i32 = 6;
+pub const PET_EVASION: i32 = 7;
+pub const PET_FOV_RADIUS: i32 = 6;
+pub const COMPANION_FOLLOW_DISTANCE: i32 = 2;  // Idle companions stay this close
+pub const COMPANION_LEASH_DISTANCE: i32 = 6;   // Following companions never stray further
+pub const COMPANION_STAIRS_RANGE: i32 = 2;     // Must be this close to follow down stairs
+
+// Pack settings
+pub const PACK_SPAWN_CHANCE: u32 = 30;        // Chance a spawn becomes a pack
+pub const PACK_MIN_FOLLOWERS: usize = 1;
+pub const PACK_MAX_FOLLOWERS: usize = 3;
+pub const PACK_SPAWN_RADIUS: i32 = 2;
+
+// Noise and stealth settings
+pub const NOISE_WALK: i32 = 4;
+pub const NOISE_SNEAK: i32 = 1;
+pub const NOISE_COMBAT: i32 = 8;
+pub const NOISE_ALARM: i32 = 25;
+pub const NOISE_ATTENUATION: i32 = 1;          // Loudness lost per tile travelled
+pub const NOISE_WAKE_CHANCE_PER_VOLUME: u32 = 15;
+pub const NOISE_ALERT_VOLUME: i32 = 5;         // Loud enough to spot the player at once
+pub const SNEAK_MOVEMENT_COST: i32 = 2;
+pub const SNEAK_ATTACK_MULTIPLIER: f32 = 2.0;
+
+// Pathfinding settings
+pub const FLEE_MAP_COEFFICIENT: f32 = 1.2;
+
+// Trap settings
+pub const TRAP_MIN_COUNT: usize = 2;
+pub const TRAP_MAX_COUNT: usize = 4;
+pub const TRAP_DETECTION_CHANCE: u32 = 25;     // Per turn, while in player's FOV
+pub const TRAP_DISARM_CHANCE: u32 = 60;
+pub const TRAP_DISARM_FUMBLE_CHANCE: u32 = 15; // Failed disarm sets the trap off
+pub const TRAP_DART_DAMAGE: i32 = 6;
+pub const TRAP_PIT_DAMAGE: i32 = 10;
+pub const DISARM_ACTION_COST: i32 = 1;
+
 // Colors (brightened significantly for visibility against black background)
 pub const COLOR_FLOOR: Color = Color::srgb(0.7, 0.7, 0.8);  // Bright blue-gray floor
 pub const COLOR_WALL: Color = Color::srgb(0.9, 0.8, 0.7);   // Bright tan walls
+pub const COLOR_DOWN_STAIRS: Color = Color::srgb(0.3, 0.5, 0.9);
+pub const COLOR_WALL_BACKGROUND: Color = Color::srgb(0.3, 0.25, 0.2); // Behind '#' in glyph mode
 pub const COLOR_PLAYER: Color = Color::srgb(0.0, 0.9, 0.0); // Bright green player
+pub const COLOR_PET: Color = Color::srgb(0.6, 0.9, 0.4);
 pub const COLOR_ENEMY: Color = Color::srgb(0.9, 0.0, 0.0);  // Bright red enemies
+pub const COLOR_GOBLIN_ARCHER: Color = Color::srgb(0.9, 0.5, 0.0);
+pub const COLOR_GOBLIN_SHAMAN: Color = Color::srgb(0.8, 0.2, 0.8);
+pub const COLOR_NECROMANCER: Color = Color::srgb(0.5, 0.2, 0.6);
+pub const COLOR_SKELETON: Color = Color::srgb(0.95, 0.95, 0.85);
+pub const COLOR_GIANT_BEETLE: Color = Color::srgb(0.45, 0.35, 0.15);
+pub const COLOR_LICH: Color = Color::srgb(0.4, 0.9, 0.95);
+pub const COLOR_TRAP_DART: Color = Color::srgb(0.8, 0.4, 0.9);
+pub const COLOR_TRAP_TELEPORT: Color = Color::srgb(0.2, 0.6, 1.0);
+pub const COLOR_TRAP_ALARM: Color = Color::srgb(1.0, 0.9, 0.1);
+pub const COLOR_TRAP_PIT: Color = Color::srgb(0.4, 0.25, 0.1);
+pub const COLOR_LANTERN_LIGHT: Color = Color::srgb(1.0, 0.95, 0.8);
+pub const COLOR_TORCH_LIGHT: Color = Color::srgb(1.0, 0.7, 0.35);
+pub const COLOR_FUNGUS_LIGHT: Color = Color::srgb(0.4, 1.0, 0.7);
+pub const COLOR_LOOK_CURSOR: Color = Color::srgba(1.0, 1.0, 0.3, 0.4);
 pub const COLOR_FOV_VISIBLE: Color = Color::srgb(1.0, 1.0, 1.0);
 pub const COLOR_FOV_EXPLORED: Color = Color::srgb(0.5, 0.5, 0.5);
 pub const COLOR_FOV_UNSEEN: Color = Color::srgb(0.0, 0.0, 0.0);
@@ -54,6 +149,63 @@ pub const COLOR_UI_BACKGROUND: Color = Color::srgba(0.0, 0.0, 0.0, 0.8);
 pub const COLOR_HEALTH_BAR_FULL: Color = Color::srgb(0.0, 0.8, 0.0);
 pub const COLOR_HEALTH_BAR_LOW: Color = Color::srgb(0.8, 0.0, 0.0);
 
+// Combat log colors
+pub const COLOR_LOG_INFO: Color = Color::srgb(0.85, 0.85, 0.85);
+pub const COLOR_LOG_MISS: Color = Color::srgb(0.6, 0.6, 0.6);
+pub const COLOR_LOG_DAMAGE: Color = Color::srgb(1.0, 0.55, 0.2);
+pub const COLOR_LOG_KILL: Color = Color::srgb(1.0, 0.2, 0.2);
+pub const COLOR_LOG_HEAL: Color = Color::srgb(0.3, 1.0, 0.4);
+
+// Combat log settings
+pub const COMBAT_LOG_HISTORY_LIMIT: usize = 500;
+pub const COMBAT_LOG_VISIBLE_LINES: usize = 8;
+pub const COMBAT_LOG_FONT_SIZE: f32 = 14.0;
+
+// Input settings
+pub const KEYBINDINGS_FILE: &str = "keybindings.cfg";
+
+// Terminal frontend settings
+pub const TERMINAL_FPS: f64 = 30.0;
+
+// Bot settings
+pub const BOT_REST_BELOW_PERCENT: i32 = 50;     // Rests when health drops below this share
+pub const BOT_DEFAULT_GAMES: usize = 100;
+pub const BOT_MAX_TURNS: u32 = 5000;            // Games still going after this many turns time out
+pub const BOT_STALL_FRAMES: u32 = 1000;         // Frames without a turn passing before a game counts as stuck
+
+// Tileset (assets/TILESET_FILE: a grid of TILESET_TILE_PIXELS squares, white
+// on transparent so sprite colors tint them)
+pub const TILESET_FILE: &str = "tileset.png";
+pub const TILESET_TILE_PIXELS: u32 = 16;
+pub const TILESET_COLUMNS: u32 = 16;
+pub const TILESET_ROWS: u32 = 4;
+
+// Glyph font (assets/GLYPH_FONT_FILE: the 256 CP437 characters in a 16x16 grid)
+pub const GLYPH_FONT_FILE: &str = "cp437.png";
+pub const GLYPH_FONT_PIXELS: u32 = 16;
+
+// Tileset indices - row 0: terrain and features
+pub const TILE_FLOOR: usize = 0;
+pub const TILE_DOWN_STAIRS: usize = 1;
+pub const TILE_TRAP_DART: usize = 2;
+pub const TILE_TRAP_TELEPORT: usize = 3;
+pub const TILE_TRAP_ALARM: usize = 4;
+pub const TILE_TRAP_PIT: usize = 5;
+pub const TILE_TORCH: usize = 6;
+pub const TILE_FUNGUS: usize = 7;
+// Row 1: walls, offset by the bitmask of neighbouring walls (N=1, E=2, S=4, W=8)
+pub const TILE_WALL_BASE: usize = 16;
+// Row 2: actors
+pub const TILE_PLAYER: usize = 32;
+pub const TILE_PET: usize = 33;
+pub const TILE_GOBLIN: usize = 34;
+pub const TILE_GOBLIN_ARCHER: usize = 35;
+pub const TILE_GOBLIN_SHAMAN: usize = 36;
+pub const TILE_NECROMANCER: usize = 37;
+pub const TILE_SKELETON: usize = 38;
+pub const TILE_GIANT_BEETLE: usize = 39;
+pub const TILE_LICH: usize = 40;
+
 // Z-layers for rendering order
 pub const Z_LAYER_FLOOR: f32 = 0.0;
 pub const Z_LAYER_ITEMS: f32 = 1.0;
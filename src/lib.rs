@@ -13,4 +13,4 @@ pub mod plugins;
 // Re-export commonly used items
 pub use constants::*;
 pub use states::{GameState, TurnState, StatesPlugin};
-pub use plugins::GameCorePlugin;
+pub use plugins::{GameCorePlugin, GameRulesPlugin, GameRenderPlugin, TerminalFrontendPlugin, headless_app, apply_action_source_args};
@@ -2,14 +2,18 @@
 ///
 /// This game is built using Bevy's Entity Component System (ECS) architecture.
 /// The game follows a turn-based roguelike pattern with procedural generation.
+/// Run with `--bot`, `--replay FILE` or `--record FILE` to change where the
+/// player's actions come from, and `--seed N` to replay a seed (see
+/// `apply_action_source_args`).
 
 use bevy::prelude::*;
 use bevy::window::{WindowResolution, PresentMode};
 use bevy::render::camera::ClearColorConfig;
-use rust_roguelike::{constants::*, StatesPlugin, GameCorePlugin, GameState};
+use rust_roguelike::{constants::*, StatesPlugin, GameCorePlugin, GameState, apply_action_source_args};
 
 fn main() {
-    App::new()
+    let mut app = App::new();
+    app
         // Bevy default plugins with custom window configuration
         .add_plugins(DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
@@ -24,9 +28,16 @@ fn main() {
         // Custom game plugins
         .add_plugins((StatesPlugin, GameCorePlugin))
         // Setup systems
-        .add_systems(Startup, setup)
-        // Run the game
-        .run();
+        .add_systems(Startup, setup);
+
+    // Keyboard, replay or bot
+    if let Err(err) = apply_action_source_args(&mut app, std::env::args().skip(1)) {
+        eprintln!("{}", err);
+        std::process::exit(2);
+    }
+
+    // Run the game
+    app.run();
 }
 
 /// Initial setup - runs once at startup
@@ -0,0 +1,75 @@
+/// Game render plugin - sprites, camera, UI panels and keyboard/mouse input
+///
+/// Everything that needs a window sits here, on top of `GameRulesPlugin`.
+
+use bevy::prelude::*;
+use crate::resources::{KeyBindings, Tileset, RenderMode, ActionSource};
+use crate::systems::{
+    camera_follow_system, apply_tile_visibility_system, hide_entities_outside_fov_system,
+    toggle_ai_debug_overlay_system, update_ai_debug_overlay_system, AiDebugOverlay,
+    spawn_combat_log_ui, combat_log_scroll_input_system, update_combat_log_ui_system,
+    spawn_hover_tooltip_ui, update_hover_tooltip_system,
+    HoveredTile, update_hovered_tile_system, click_to_travel_system,
+    load_keybindings_system, keyboard_actions_system,
+    LookMode, spawn_look_ui, look_input_system, update_look_display_system,
+    load_tileset_system, apply_tileset_system, toggle_render_mode_system,
+    update_glyph_backgrounds_system, update_glyph_text_system,
+    attach_sprites_system, update_sprite_positions_system, interrupt_travel_system,
+};
+use crate::plugins::game_rules::GameRulesSet;
+use crate::states::GameState;
+
+/// Draws the game and reads the player's keyboard and mouse
+pub struct GameRenderPlugin;
+
+impl Plugin for GameRenderPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // Resources
+            .init_resource::<Tileset>()
+            .init_resource::<RenderMode>()
+            .init_resource::<AiDebugOverlay>()
+            .init_resource::<HoveredTile>()
+            .init_resource::<KeyBindings>()
+            .init_resource::<LookMode>()
+            // UI setup
+            .add_systems(Startup, (spawn_combat_log_ui, spawn_hover_tooltip_ui, spawn_look_ui, load_keybindings_system, load_tileset_system))
+            // Player input (look mode swallows actions while the cursor is out)
+            .add_systems(Update, (
+                keyboard_actions_system,
+                look_input_system,
+                click_to_travel_system,
+            ).chain()
+                .in_set(GameRulesSet::Input)
+                .before(interrupt_travel_system)
+                .run_if(resource_equals(ActionSource::Keyboard)))
+            // Sprites follow the game state; tileset or glyph art (F2 switches between them)
+            .add_systems(Update, (
+                attach_sprites_system,
+                update_sprite_positions_system,
+                camera_follow_system,
+                apply_tile_visibility_system,
+                hide_entities_outside_fov_system,
+                toggle_render_mode_system,
+                apply_tileset_system,
+                update_glyph_backgrounds_system,
+                update_glyph_text_system,
+            ).chain().after(GameRulesSet::Simulation))
+            // Combat log UI (also usable while paused)
+            .add_systems(Update, (
+                combat_log_scroll_input_system,
+                update_combat_log_ui_system,
+            ).chain())
+            // Hover tooltip and look mode display
+            .add_systems(Update, (
+                update_hovered_tile_system,
+                update_hover_tooltip_system,
+                update_look_display_system,
+            ).chain().run_if(in_state(GameState::Playing)))
+            // AI debug overlay
+            .add_systems(Update, (
+                toggle_ai_debug_overlay_system,
+                update_ai_debug_overlay_system,
+            ).chain().after(attach_sprites_system).run_if(in_state(GameState::Playing)));
+    }
+}
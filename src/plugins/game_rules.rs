@@ -0,0 +1,315 @@
+/// Game rules plugin - turns, movement, combat, AI and FOV, with nothing drawn
+///
+/// Runs under `MinimalPlugins`, so a whole game can be stepped without a
+/// window or GPU. The player's actions go into `ActionInput` during
+/// `GameRulesSet::Input`, from the keyboard (see `GameRenderPlugin`), a replay
+/// or the built-in bot, as chosen by the `ActionSource` resource. Frontends
+/// draw the results after `GameRulesSet::Simulation`.
+
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin as BevyStatesPlugin;
+use std::collections::HashSet;
+use crate::components::{
+    Player, Position, Renderable, Viewshed, Health, Regeneration, CombatStats, DamageType, Weapon, Name, Faction,
+    LightSource,
+};
+use crate::resources::{
+    CurrentMap, VisibilityMap, PlayerActionPoints, CombatLog, DijkstraMaps, Bestiary, FactionTable,
+    Depth, ActionInput, EntityMemory, LightMap, ActionSource, ActionReplay, ActionRecording, GameRng,
+};
+use crate::systems::{
+    player_input_system, apply_movement_system,
+    calculate_fov_system, update_visibility_map_system, update_entity_memory_system,
+    check_turn_end_system, start_player_turn_system, enemy_turn_system,
+    enemy_action_system, update_dijkstra_maps_system, update_ai_state_system,
+    break_leaderless_packs_system,
+    emit_action_noise_system, hear_noise_system, toggle_sneak_system, NoiseEvent,
+    resolve_spell_system, CastSpell, record_grudges_system,
+    player_attack_input_system, execute_attack_system, resolve_attack_system,
+    record_combat_events_system, remove_dead_entities_system, check_player_death_system,
+    spawn_enemies_system,
+    spawn_traps_system, detect_traps_system, trigger_traps_system,
+    player_disarm_input_system, execute_disarm_system,
+    PendingAttack, PendingDisarm, EntityMoved, AttackIntent, CombatSequence,
+    AttackMissed, DamageDealt, EntityKilled, HealApplied,
+    spawn_starting_pet, companion_command_input_system, companion_target_system,
+    companion_action_system,
+    PendingDescend, spawn_map_tiles, player_descend_input_system, descend_stairs_system,
+    boss_phase_system, check_boss_victory_system,
+    TravelPlan, interrupt_travel_system, travel_step_system,
+    clear_action_input_system, replay_actions_system, record_actions_system, save_recording_system,
+    bot_actions_system,
+    AutoExplore, auto_explore_input_system, auto_explore_step_system,
+    Resting, player_wait_input_system, rest_input_system, rest_turn_system,
+    natural_regeneration_system,
+    spawn_light_sources_system, update_light_map_system,
+};
+use crate::systems::movement::PendingMovement;
+use crate::states::{GameState, TurnState};
+use crate::constants::*;
+
+/// Resource to track if game has been initialized
+#[derive(Resource, Default)]
+struct GameInitialized(bool);
+
+/// Where frontends hook into a frame of the game
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GameRulesSet {
+    /// Fill `ActionInput` with this frame's player actions (player turn only)
+    Input,
+    /// Player turn, enemy turn and combat resolution
+    Simulation,
+}
+
+/// All of the game's rules, without any rendering, UI or keyboard handling
+pub struct GameRulesPlugin;
+
+impl Plugin for GameRulesPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // State management
+            .init_state::<GameState>()
+            .init_state::<TurnState>()
+            .configure_sets(Update, (GameRulesSet::Input, GameRulesSet::Simulation).chain())
+            .configure_sets(Update, GameRulesSet::Input
+                .run_if(in_state(GameState::Playing).and(in_state(TurnState::PlayerTurn))))
+            // Resources
+            .init_resource::<PendingMovement>()
+            .init_resource::<GameInitialized>()
+            .init_resource::<VisibilityMap>()
+            .init_resource::<EntityMemory>()
+            .init_resource::<LightMap>()
+            .init_resource::<PlayerActionPoints>()
+            .init_resource::<PendingAttack>()
+            .init_resource::<CombatSequence>()
+            .init_resource::<GameRng>()
+            .init_resource::<CombatLog>()
+            .init_resource::<PendingDisarm>()
+            .init_resource::<DijkstraMaps>()
+            .init_resource::<Bestiary>()
+            .init_resource::<FactionTable>()
+            .init_resource::<PendingDescend>()
+            .init_resource::<Depth>()
+            .init_resource::<TravelPlan>()
+            .init_resource::<ActionInput>()
+            .init_resource::<ActionSource>()
+            .init_resource::<ActionReplay>()
+            .init_resource::<AutoExplore>()
+            .init_resource::<Resting>()
+            // Events
+            .add_event::<EntityMoved>()
+            .add_event::<AttackIntent>()
+            .add_event::<AttackMissed>()
+            .add_event::<DamageDealt>()
+            .add_event::<EntityKilled>()
+            .add_event::<HealApplied>()
+            .add_event::<NoiseEvent>()
+            .add_event::<CastSpell>()
+            // Action sources other than the keyboard
+            .add_systems(Update, (
+                replay_actions_system.run_if(resource_equals(ActionSource::Replay)),
+                bot_actions_system.run_if(resource_equals(ActionSource::Bot)),
+            ).in_set(GameRulesSet::Input))
+            // Click-to-travel steps become move actions (frontends read their
+            // input before this, so a keypress can cancel travel)
+            .add_systems(Update, (
+                interrupt_travel_system,
+                travel_step_system,
+            ).chain().in_set(GameRulesSet::Input))
+            .add_systems(Update, record_actions_system
+                .after(GameRulesSet::Input)
+                .before(GameRulesSet::Simulation)
+                .run_if(resource_exists::<ActionRecording>)
+                .run_if(in_state(GameState::Playing).and(in_state(TurnState::PlayerTurn))))
+            // One-time setup when first entering Playing state
+            .add_systems(OnEnter(GameState::Playing), initialize_game)
+            // Player turn systems (run during Playing AND PlayerTurn state)
+            .add_systems(Update, (
+                // Turning actions into intents
+                (
+                    player_input_system,
+                    auto_explore_input_system,
+                    auto_explore_step_system,
+                    player_wait_input_system,
+                    rest_input_system,
+                    rest_turn_system,
+                    player_attack_input_system,
+                    player_disarm_input_system,
+                    toggle_sneak_system,
+                    companion_command_input_system,
+                    player_descend_input_system,
+                ).chain(),
+                // Action execution
+                apply_movement_system,
+                trigger_traps_system,
+                execute_attack_system,
+                execute_disarm_system,
+                descend_stairs_system,
+                // Lighting and FOV systems (run after movement)
+                update_light_map_system,
+                calculate_fov_system,
+                update_visibility_map_system,
+                update_entity_memory_system,
+                detect_traps_system,
+                // Check if turn should end
+                check_turn_end_system,
+            ).chain()
+                .in_set(GameRulesSet::Simulation)
+                .run_if(in_state(GameState::Playing).and(in_state(TurnState::PlayerTurn))))
+            // Enemy turn systems
+            .add_systems(Update, (
+                update_dijkstra_maps_system,
+                update_ai_state_system,
+                companion_action_system,
+                enemy_action_system,
+                resolve_spell_system,
+                enemy_turn_system,
+            ).chain()
+                .in_set(GameRulesSet::Simulation)
+                .run_if(in_state(GameState::Playing).and(in_state(TurnState::EnemyTurn))))
+            // Combat resolution (shared by both turns), logging and death handling
+            .add_systems(Update, (
+                resolve_attack_system,
+                record_combat_events_system,
+                record_grudges_system,
+                companion_target_system,
+                boss_phase_system,
+                break_leaderless_packs_system,
+                emit_action_noise_system,
+                hear_noise_system,
+                check_boss_victory_system,
+                remove_dead_entities_system,
+                check_player_death_system,
+            ).chain()
+                .after(execute_attack_system)
+                .after(execute_disarm_system)
+                .after(companion_action_system)
+                .after(enemy_action_system)
+                .after(resolve_spell_system)
+                .before(enemy_turn_system)
+                .in_set(GameRulesSet::Simulation)
+                .run_if(in_state(GameState::Playing)))
+            // Actions last for the frame they were given in
+            .add_systems(Last, (
+                clear_action_input_system,
+                save_recording_system.run_if(resource_exists::<ActionRecording>),
+            ))
+            // Turn transition events
+            .add_systems(OnEnter(TurnState::PlayerTurn), (start_player_turn_system, natural_regeneration_system));
+    }
+}
+
+/// An app with only the game rules and the plugins they need, starting a game
+///
+/// Nothing is drawn and no keys are read: step it with `app.update()`, with
+/// the player's actions coming from `source`.
+pub fn headless_app(source: ActionSource) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, BevyStatesPlugin, GameRulesPlugin));
+    app.insert_resource(source);
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Playing);
+    app
+}
+
+/// Pick the action source from command line arguments
+///
+/// `--bot` lets the built-in bot play, `--replay FILE` plays back a recorded
+/// game (on its recorded seed) and `--record FILE` saves the player's actions
+/// to FILE on exit. `--seed N` starts the game from seed N, e.g. to watch a
+/// game the bot runner reported.
+pub fn apply_action_source_args(app: &mut App, args: impl IntoIterator<Item = String>) -> Result<(), String> {
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--bot" => {
+                app.insert_resource(ActionSource::Bot);
+            }
+            "--replay" => {
+                let path = args.next().ok_or("--replay needs a file")?;
+                let text = std::fs::read_to_string(&path).map_err(|err| format!("{}: {}", path, err))?;
+                let replay = ActionReplay::parse(&text).map_err(|err| format!("{}: {}", path, err))?;
+                if let Some(seed) = replay.seed {
+                    app.insert_resource(GameRng::new(seed));
+                }
+                app.insert_resource(replay).insert_resource(ActionSource::Replay);
+            }
+            "--seed" => {
+                let seed = args.next().ok_or("--seed needs a number")?;
+                let seed = seed.parse().map_err(|_| format!("bad seed '{}'", seed))?;
+                app.insert_resource(GameRng::new(seed));
+            }
+            "--record" => {
+                let path = args.next().ok_or("--record needs a file")?;
+                app.insert_resource(ActionRecording::new(path));
+            }
+            other => return Err(format!("unknown argument '{}'", other)),
+        }
+    }
+
+    Ok(())
+}
+
+/// Initialize the game only once
+fn initialize_game(
+    mut commands: Commands,
+    mut initialized: ResMut<GameInitialized>,
+    bestiary: Res<Bestiary>,
+    mut rng: ResMut<GameRng>,
+) {
+    // Only initialize once
+    if initialized.0 {
+        info!("Game already initialized, skipping setup");
+        return;
+    }
+
+    // Create map
+    let map = CurrentMap::for_depth(1, &mut rng);
+
+    // Spawn map tiles first (before inserting resource)
+    spawn_map_tiles(&mut commands, &map);
+    info!("Map tiles spawned with FOV support!");
+
+    // Spawn player
+    commands.spawn((
+        Player,
+        Faction::Player,
+        Position::new(10, 10),
+        Name::new("Hero"),
+        Health::new(PLAYER_STARTING_HEALTH),
+        Regeneration::new(PLAYER_REGEN_TURNS),
+        CombatStats::new(PLAYER_ATTACK_POWER, PLAYER_DEFENSE)
+            .with_accuracy(PLAYER_ACCURACY)
+            .with_evasion(PLAYER_EVASION),
+        Weapon::new("Short Sword", DamageType::Physical),
+        Renderable::new(COLOR_PLAYER).with_tile(TILE_PLAYER).with_glyph('@'),
+        Viewshed::new(FOV_RADIUS),
+        LightSource::new(PLAYER_LANTERN_RADIUS, COLOR_LANTERN_LIGHT, 1.0),
+    ));
+    info!("Player spawned at (10, 10) with {} HP and FOV radius {}",
+          PLAYER_STARTING_HEALTH, FOV_RADIUS);
+
+    // The player's starting pet
+    let pet_pos = spawn_starting_pet(&mut commands, &map, Position::new(10, 10));
+
+    // Nothing else goes where the player and pet stand
+    let occupied: HashSet<Position> = std::iter::once(Position::new(10, 10)).chain(pet_pos).collect();
+
+    // Spawn enemies (before inserting map resource)
+    spawn_enemies_system(commands.reborrow(), &map, &bestiary, &occupied, &mut rng);
+
+    // Place concealed traps
+    spawn_traps_system(commands.reborrow(), &map, &occupied, &mut rng);
+
+    // Torches and glowing fungi
+    spawn_light_sources_system(commands.reborrow(), &map, Position::new(10, 10), &mut rng);
+
+    // Now insert the map resource
+    commands.insert_resource(map);
+    info!("Map created!");
+
+    // Mark as initialized
+    initialized.0 = true;
+}
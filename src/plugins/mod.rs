@@ -3,5 +3,11 @@
 /// Plugins bundle related systems, components, and resources.
 
 pub mod game_core;
+pub mod game_rules;
+pub mod game_render;
+pub mod terminal;
 
 pub use game_core::GameCorePlugin;
+pub use game_rules::{GameRulesPlugin, GameRulesSet, headless_app, apply_action_source_args};
+pub use game_render::GameRenderPlugin;
+pub use terminal::TerminalFrontendPlugin;
@@ -0,0 +1,369 @@
+/// Terminal frontend plugin - drawing the game as text and reading keys from the terminal
+///
+/// Used by the `terminal` binary on top of `GameRulesPlugin`, in place of the
+/// windowed `GameRenderPlugin`. Terminal key events are turned into `KeyCode`
+/// presses, so the same keybindings and input actions apply as in the
+/// windowed build. The map is drawn straight from the game's visibility,
+/// light and memory resources.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use bevy::prelude::*;
+use bevy::input::InputSystem;
+use ratatui::DefaultTerminal;
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::{self, Event, KeyCode as TermKey, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color as TermColor, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use crate::components::{Player, Position, Renderable, Health, Name, Sneaking, Memorable, Concealed};
+use crate::resources::{
+    CombatLog, Depth, PlayerActionPoints, VisibilityMap, VisibilityState, LightMap, EntityMemory, CurrentMap,
+    FactionTable, KeyBindings, ActionSource,
+};
+use crate::systems::fov::{MapTile, dimmed};
+use crate::systems::look::{LookMode, look_input_system};
+use crate::systems::input::{load_keybindings_system, keyboard_actions_system};
+use crate::systems::mouse::interrupt_travel_system;
+use crate::systems::ui::{
+    Onlooker, Described, describe_tile, describe_visible_entities, combat_log_scroll_input_system,
+};
+use crate::plugins::game_rules::GameRulesSet;
+use crate::states::GameState;
+use crate::constants::{COMBAT_LOG_VISIBLE_LINES, COLOR_LOOK_CURSOR, COLOR_UI_TEXT};
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+/// Draws to and reads keys from the terminal instead of a window
+///
+/// Add it alongside `GameRulesPlugin` (not `GameCorePlugin`): it brings its
+/// own keyboard actions, look mode and combat log scrolling.
+///
+/// The terminal is set up by the caller (see `ratatui::try_init`), so a
+/// missing TTY can be reported before the app is built.
+pub struct TerminalFrontendPlugin {
+    terminal: Mutex<Option<DefaultTerminal>>,
+}
+
+impl TerminalFrontendPlugin {
+    pub fn new(terminal: DefaultTerminal) -> Self {
+        Self {
+            terminal: Mutex::new(Some(terminal)),
+        }
+    }
+}
+
+impl Plugin for TerminalFrontendPlugin {
+    fn build(&self, app: &mut App) {
+        let terminal = self
+            .terminal
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .take()
+            .expect("the terminal frontend can only be added once");
+
+        app
+            .insert_non_send_resource(TerminalScreen { terminal })
+            .init_resource::<KeyBindings>()
+            .init_resource::<LookMode>()
+            .add_systems(Startup, load_keybindings_system)
+            .add_systems(PreUpdate, terminal_input_system.after(InputSystem))
+            // Player input (look mode swallows actions while the cursor is out)
+            .add_systems(Update, (
+                keyboard_actions_system,
+                look_input_system,
+            ).chain()
+                .in_set(GameRulesSet::Input)
+                .before(interrupt_travel_system)
+                .run_if(resource_equals(ActionSource::Keyboard)))
+            .add_systems(Update, combat_log_scroll_input_system)
+            .add_systems(Last, draw_terminal_system);
+    }
+}
+
+/// The terminal in raw mode, put back to normal when dropped
+pub struct TerminalScreen {
+    terminal: DefaultTerminal,
+}
+
+impl Drop for TerminalScreen {
+    fn drop(&mut self) {
+        ratatui::restore();
+    }
+}
+
+// ============================================================================
+// INPUT
+// ============================================================================
+
+const LETTER_KEYS: [KeyCode; 26] = [
+    KeyCode::KeyA, KeyCode::KeyB, KeyCode::KeyC, KeyCode::KeyD, KeyCode::KeyE, KeyCode::KeyF,
+    KeyCode::KeyG, KeyCode::KeyH, KeyCode::KeyI, KeyCode::KeyJ, KeyCode::KeyK, KeyCode::KeyL,
+    KeyCode::KeyM, KeyCode::KeyN, KeyCode::KeyO, KeyCode::KeyP, KeyCode::KeyQ, KeyCode::KeyR,
+    KeyCode::KeyS, KeyCode::KeyT, KeyCode::KeyU, KeyCode::KeyV, KeyCode::KeyW, KeyCode::KeyX,
+    KeyCode::KeyY, KeyCode::KeyZ,
+];
+
+/// Digits count as numpad keys: terminals don't tell the two apart, and the
+/// numpad layout is the only default with diagonal moves (chorded arrow or
+/// WASD diagonals need two keys held at once, which terminals can't report)
+const NUMPAD_KEYS: [KeyCode; 10] = [
+    KeyCode::Numpad0, KeyCode::Numpad1, KeyCode::Numpad2, KeyCode::Numpad3, KeyCode::Numpad4,
+    KeyCode::Numpad5, KeyCode::Numpad6, KeyCode::Numpad7, KeyCode::Numpad8, KeyCode::Numpad9,
+];
+
+const FUNCTION_KEYS: [KeyCode; 12] = [
+    KeyCode::F1, KeyCode::F2, KeyCode::F3, KeyCode::F4, KeyCode::F5, KeyCode::F6,
+    KeyCode::F7, KeyCode::F8, KeyCode::F9, KeyCode::F10, KeyCode::F11, KeyCode::F12,
+];
+
+/// The physical key behind a terminal key event (shifted symbols map to
+/// their key, digits to the numpad)
+pub fn key_code(key: TermKey) -> Option<KeyCode> {
+    match key {
+        TermKey::Char(c) if c.is_ascii_alphabetic() => {
+            Some(LETTER_KEYS[(c.to_ascii_lowercase() as u8 - b'a') as usize])
+        }
+        TermKey::Char(c) if c.is_ascii_digit() => Some(NUMPAD_KEYS[(c as u8 - b'0') as usize]),
+        TermKey::Char(' ') => Some(KeyCode::Space),
+        TermKey::Char('.' | '>') => Some(KeyCode::Period),
+        TermKey::Char(',' | '<') => Some(KeyCode::Comma),
+        TermKey::Char(';' | ':') => Some(KeyCode::Semicolon),
+        TermKey::Char('/' | '?') => Some(KeyCode::Slash),
+        TermKey::Char('\'' | '"') => Some(KeyCode::Quote),
+        TermKey::Char('-' | '_') => Some(KeyCode::Minus),
+        TermKey::Char('=' | '+') => Some(KeyCode::Equal),
+        TermKey::Char('[' | '{') => Some(KeyCode::BracketLeft),
+        TermKey::Char(']' | '}') => Some(KeyCode::BracketRight),
+        TermKey::Char('\\' | '|') => Some(KeyCode::Backslash),
+        TermKey::Char('`' | '~') => Some(KeyCode::Backquote),
+        TermKey::Up => Some(KeyCode::ArrowUp),
+        TermKey::Down => Some(KeyCode::ArrowDown),
+        TermKey::Left => Some(KeyCode::ArrowLeft),
+        TermKey::Right => Some(KeyCode::ArrowRight),
+        TermKey::Enter => Some(KeyCode::Enter),
+        TermKey::Esc => Some(KeyCode::Escape),
+        TermKey::Tab => Some(KeyCode::Tab),
+        TermKey::Backspace => Some(KeyCode::Backspace),
+        TermKey::Home => Some(KeyCode::Home),
+        TermKey::End => Some(KeyCode::End),
+        TermKey::PageUp => Some(KeyCode::PageUp),
+        TermKey::PageDown => Some(KeyCode::PageDown),
+        TermKey::Insert => Some(KeyCode::Insert),
+        TermKey::Delete => Some(KeyCode::Delete),
+        TermKey::F(n) if (1..=12).contains(&n) => Some(FUNCTION_KEYS[n as usize - 1]),
+        _ => None,
+    }
+}
+
+/// Feed terminal key presses into `ButtonInput<KeyCode>` (Ctrl-C or Ctrl-Q quits)
+///
+/// Terminals report presses but not releases, so every key is released
+/// again on the following frame.
+pub fn terminal_input_system(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    keyboard.release_all();
+
+    while event::poll(Duration::ZERO).unwrap_or(false) {
+        let Ok(Event::Key(key)) = event::read() else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        let quit = key.modifiers.contains(KeyModifiers::CONTROL)
+            && matches!(key.code, TermKey::Char('c' | 'q'));
+        if quit {
+            exit.send(AppExit::Success);
+            return;
+        }
+
+        if let Some(code) = key_code(key.code) {
+            keyboard.press(code);
+        }
+    }
+}
+
+// ============================================================================
+// DRAWING
+// ============================================================================
+
+fn term_color(color: Color) -> TermColor {
+    let c = color.to_srgba();
+    TermColor::Rgb(
+        (c.red * 255.0) as u8,
+        (c.green * 255.0) as u8,
+        (c.blue * 255.0) as u8,
+    )
+}
+
+/// One character cell of the map view
+#[derive(Clone, Copy)]
+struct MapCell {
+    glyph: char,
+    fg: Color,
+    bg: Option<Color>,
+}
+
+/// Write the map cells centred on `center` into the buffer area
+fn draw_map(buf: &mut Buffer, area: Rect, cells: &HashMap<Position, MapCell>, center: Position, cursor: Option<Position>) {
+    let left = center.x - area.width as i32 / 2;
+    let top = center.y + area.height as i32 / 2;
+
+    for row in 0..area.height {
+        for column in 0..area.width {
+            // Rows go down the screen, map y goes up (north)
+            let pos = Position::new(left + column as i32, top - row as i32);
+            let Some(cell) = buf.cell_mut((area.x + column, area.y + row)) else {
+                continue;
+            };
+
+            if let Some(map_cell) = cells.get(&pos) {
+                cell.set_char(map_cell.glyph).set_fg(term_color(map_cell.fg));
+                if let Some(bg) = map_cell.bg {
+                    cell.set_bg(term_color(bg));
+                }
+            }
+            if cursor == Some(pos) {
+                cell.set_bg(term_color(COLOR_LOOK_CURSOR.with_alpha(1.0)));
+            }
+        }
+    }
+}
+
+/// Draw the map, a status panel and the combat log to the terminal
+///
+/// Seen tiles are lit by the light map, explored ones dimmed; creatures show
+/// while in view, and remembered things dimmed where they were last seen.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn draw_terminal_system(
+    mut screen: NonSendMut<TerminalScreen>,
+    map: Option<Res<CurrentMap>>,
+    visibility_map: Res<VisibilityMap>,
+    light_map: Res<LightMap>,
+    memory: Res<EntityMemory>,
+    factions: Res<FactionTable>,
+    combat_log: Res<CombatLog>,
+    depth: Res<Depth>,
+    action_points: Res<PlayerActionPoints>,
+    look_mode: Res<LookMode>,
+    game_state: Res<State<GameState>>,
+    tile_query: Query<(&MapTile, &Renderable)>,
+    entity_query: Query<(Entity, &Position, &Renderable, Has<Memorable>), (Without<MapTile>, Without<Concealed>)>,
+    player_query: Query<(Entity, (&Position, &Name, &Health, Has<Sneaking>), Onlooker), With<Player>>,
+    described_query: Query<Described, Without<Concealed>>,
+) {
+    let player = player_query.get_single().ok();
+
+    // Tiles, shaded for fog of war and light
+    let mut cells: HashMap<Position, MapCell> = HashMap::new();
+    for (tile, renderable) in tile_query.iter() {
+        let pos = tile.position;
+        let (fg, bg) = match visibility_map.get(&pos) {
+            VisibilityState::Unseen => continue,
+            VisibilityState::Explored => (dimmed(renderable.color), dimmed(renderable.background)),
+            VisibilityState::Visible => (light_map.shade(renderable.color, pos), renderable.background),
+        };
+        let bg = (renderable.background.alpha() != 0.0).then_some(bg);
+        cells.insert(pos, MapCell { glyph: renderable.glyph, fg, bg });
+    }
+
+    // Entities on top, lowest layer first: the player, what they can see and
+    // what they remember
+    let mut shown: Vec<(Position, &Renderable, Color)> = entity_query
+        .iter()
+        .filter_map(|(entity, pos, renderable, memorable)| {
+            let (player, _, (viewshed, ..)) = player?;
+            if entity == player || viewshed.can_see(pos) {
+                Some((*pos, renderable, renderable.color))
+            } else if memorable && memory.remembers(entity, *pos) {
+                Some((*pos, renderable, dimmed(renderable.color)))
+            } else {
+                None
+            }
+        })
+        .collect();
+    shown.sort_by(|a, b| a.1.layer.total_cmp(&b.1.layer));
+    for (pos, renderable, fg) in shown {
+        let bg = cells.get(&pos).and_then(|cell| cell.bg);
+        cells.insert(pos, MapCell { glyph: renderable.glyph, fg, bg });
+    }
+
+    // Status panel
+    let mut status: Vec<Line> = Vec::new();
+    let center = match player {
+        Some((_, (pos, name, health, sneaking), _)) => {
+            status.push(Line::from(name.0.clone()));
+            status.push(Line::from(format!("HP {}/{}", health.current, health.max)));
+            status.push(Line::from(format!("AP {}/{}", action_points.current, action_points.max)));
+            status.push(Line::from(format!("Depth {}", depth.0)));
+            if sneaking {
+                status.push(Line::from("Sneaking"));
+            }
+            *pos
+        }
+        None => Position::new(0, 0),
+    };
+    status.push(Line::from(""));
+    match game_state.get() {
+        GameState::Paused => status.push(Line::from("PAUSED (Esc)")),
+        GameState::GameOver => status.push(Line::from("You have died. Ctrl-C to quit.")),
+        GameState::Victory => status.push(Line::from("You are victorious! Ctrl-C to quit.")),
+        _ => {}
+    }
+
+    // Look mode description
+    if let (Some(cursor), Some(map), Some((_, _, onlooker))) = (look_mode.cursor, map.as_deref(), player) {
+        let visible_things = describe_visible_entities(cursor, onlooker, &factions, &described_query);
+        let lines = describe_tile(map, &visibility_map, &memory, cursor, &visible_things)
+            .unwrap_or_else(|| vec!["Unexplored".to_string()]);
+        status.push(Line::from("Looking (; to stop)"));
+        status.extend(lines.into_iter().map(Line::from));
+    }
+
+    // Combat log
+    let log_lines: Vec<Line> = combat_log
+        .visible_entries(COMBAT_LOG_VISIBLE_LINES)
+        .iter()
+        .map(|entry| Line::styled(entry.text.clone(), Style::default().fg(term_color(entry.kind.color()))))
+        .collect();
+
+    let cursor = look_mode.cursor;
+    let text_style = Style::default().fg(term_color(COLOR_UI_TEXT));
+    let result = screen.terminal.draw(|frame| {
+        let [top, log_area] = Layout::vertical([
+            Constraint::Min(5),
+            Constraint::Length(COMBAT_LOG_VISIBLE_LINES as u16 + 2),
+        ])
+        .areas(frame.area());
+        let [map_area, status_area] = Layout::horizontal([Constraint::Min(20), Constraint::Length(28)]).areas(top);
+
+        let map_block = Block::default().borders(Borders::ALL).title("Dungeon");
+        let map_inner = map_block.inner(map_area);
+        frame.render_widget(map_block, map_area);
+        draw_map(frame.buffer_mut(), map_inner, &cells, center, cursor);
+
+        frame.render_widget(
+            Paragraph::new(status)
+                .style(text_style)
+                .wrap(Wrap { trim: true })
+                .block(Block::default().borders(Borders::ALL).title("Status")),
+            status_area,
+        );
+        frame.render_widget(
+            Paragraph::new(log_lines).block(Block::default().borders(Borders::ALL).title("Combat Log")),
+            log_area,
+        );
+    });
+
+    if let Err(err) = result {
+        error!("Failed to draw to the terminal: {}", err);
+    }
+}
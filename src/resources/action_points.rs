@@ -8,6 +8,8 @@ use crate::constants::PLAYER_STARTING_ACTION_POINTS;
 pub struct PlayerActionPoints {
     pub current: i32,
     pub max: i32,
+    /// Points overspent by slow actions, paid back from following turns
+    pub debt: i32,
 }
 
 impl Default for PlayerActionPoints {
@@ -15,14 +17,16 @@ impl Default for PlayerActionPoints {
         Self {
             current: PLAYER_STARTING_ACTION_POINTS,
             max: PLAYER_STARTING_ACTION_POINTS,
+            debt: 0,
         }
     }
 }
 
 impl PlayerActionPoints {
-    /// Reset action points to maximum (called at start of player turn)
+    /// Reset action points to maximum minus any debt (called at start of player turn)
     pub fn reset(&mut self) {
-        self.current = self.max;
+        self.current = (self.max - self.debt).max(0);
+        self.debt = (self.debt - self.max).max(0);
     }
 
     /// Check if player can afford an action
@@ -30,8 +34,9 @@ impl PlayerActionPoints {
         self.current >= cost
     }
 
-    /// Spend action points
+    /// Spend action points; any shortfall becomes debt for later turns
     pub fn spend(&mut self, cost: i32) {
+        self.debt += (cost - self.current).max(0);
         self.current = (self.current - cost).max(0);
     }
 
@@ -0,0 +1,197 @@
+/// Action sources - where the player's actions come from
+///
+/// The keyboard is the usual source; a replay or the built-in bot can drive
+/// the player instead. Whatever the source, its actions end up in
+/// `ActionInput` for the game rules to act on.
+///
+/// Replay text has one line per frame of the player's turn: the names of the
+/// actions given that frame (as in the keybindings file), comma separated,
+/// or `idle N` for N frames without any. `#` starts a comment. A `seed N`
+/// line gives the `GameRng` seed the game was played with, so the replay
+/// plays out on the same levels with the same rolls.
+
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use crate::resources::PlayerAction;
+
+/// Which source fills `ActionInput` during the player's turn
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ActionSource {
+    #[default]
+    Keyboard,
+    /// Frames from the `ActionReplay` resource
+    Replay,
+    /// The built-in bot
+    Bot,
+}
+
+// ============================================================================
+// REPLAYS
+// ============================================================================
+
+/// Recorded frames of player actions, played back in order
+#[derive(Resource, Debug, Default)]
+pub struct ActionReplay {
+    /// Seed of the recorded game, if the replay has one
+    pub seed: Option<u64>,
+    frames: VecDeque<Vec<PlayerAction>>,
+}
+
+impl ActionReplay {
+    /// Read replay text, failing on the first line that doesn't make sense
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut frames = VecDeque::new();
+        let mut seed = None;
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(value) = line.strip_prefix("seed ") {
+                let value: u64 = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("line {}: bad seed '{}'", number + 1, value.trim()))?;
+                seed = Some(value);
+                continue;
+            }
+
+            if let Some(count) = line.strip_prefix("idle ") {
+                let count: usize = count
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("line {}: bad idle count '{}'", number + 1, count.trim()))?;
+                frames.extend(std::iter::repeat_with(Vec::new).take(count));
+                continue;
+            }
+
+            let frame = line
+                .split(',')
+                .map(|name| {
+                    let name = name.trim();
+                    PlayerAction::from_name(name)
+                        .ok_or_else(|| format!("line {}: unknown action '{}'", number + 1, name))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            frames.push_back(frame);
+        }
+
+        Ok(Self { seed, frames })
+    }
+
+    /// Actions for the next frame (None once the replay is over)
+    pub fn next_frame(&mut self) -> Option<Vec<PlayerAction>> {
+        self.frames.pop_front()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+/// Every frame of player actions so far, for saving as a replay
+///
+/// Only kept while this resource exists; it is written to `path` when the
+/// game exits.
+#[derive(Resource, Debug)]
+pub struct ActionRecording {
+    pub path: String,
+    frames: Vec<Vec<PlayerAction>>,
+}
+
+impl ActionRecording {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            frames: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, actions: &[PlayerAction]) {
+        self.frames.push(actions.to_vec());
+    }
+
+    /// Replay text for the recorded frames (runs of idle frames collapsed),
+    /// headed by the game's seed
+    pub fn to_text(&self, seed: u64) -> String {
+        let mut text = String::from("# Recorded player actions, one line per frame of the player's turn\n");
+        text.push_str(&format!("seed {}\n", seed));
+        let mut idle = 0;
+
+        for frame in &self.frames {
+            if frame.is_empty() {
+                idle += 1;
+                continue;
+            }
+            if idle > 0 {
+                text.push_str(&format!("idle {}\n", idle));
+                idle = 0;
+            }
+            let names: Vec<&str> = frame.iter().map(|action| action.name()).collect();
+            text.push_str(&names.join(", "));
+            text.push('\n');
+        }
+        if idle > 0 {
+            text.push_str(&format!("idle {}\n", idle));
+        }
+
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play_back(mut replay: ActionReplay) -> Vec<Vec<PlayerAction>> {
+        std::iter::from_fn(|| replay.next_frame()).collect()
+    }
+
+    #[test]
+    fn recording_round_trips_through_replay_text() {
+        let frames = vec![
+            vec![PlayerAction::MoveNorth],
+            vec![],
+            vec![],
+            vec![],
+            vec![PlayerAction::Attack, PlayerAction::MoveEast],
+            vec![],
+        ];
+        let mut recording = ActionRecording::new("unused");
+        for frame in &frames {
+            recording.record(frame);
+        }
+
+        let text = recording.to_text(1234);
+        assert!(text.contains("seed 1234\n"));
+        assert!(text.contains("idle 3\n"));
+        assert!(text.ends_with("idle 1\n"));
+
+        let replay = ActionReplay::parse(&text).unwrap();
+        assert_eq!(replay.seed, Some(1234));
+        assert_eq!(play_back(replay), frames);
+    }
+
+    #[test]
+    fn comments_and_blank_lines_are_skipped() {
+        let text = "# header\n\nwait  # resting a moment\n  \nidle 2\n";
+        let replay = ActionReplay::parse(text).unwrap();
+
+        assert_eq!(replay.seed, None);
+        assert_eq!(play_back(replay), vec![vec![PlayerAction::Wait], vec![], vec![]]);
+    }
+
+    #[test]
+    fn bad_lines_are_reported_with_their_number() {
+        let err = ActionReplay::parse("wait\nmove_north, fly\n").unwrap_err();
+        assert_eq!(err, "line 2: unknown action 'fly'");
+
+        let err = ActionReplay::parse("idle many\n").unwrap_err();
+        assert_eq!(err, "line 1: bad idle count 'many'");
+
+        let err = ActionReplay::parse("# header\nseed -1\n").unwrap_err();
+        assert_eq!(err, "line 2: bad seed '-1'");
+    }
+}
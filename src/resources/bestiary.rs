@@ -0,0 +1,316 @@
+/// Monster templates - data describing every kind of monster
+///
+/// Stats, resistances, behaviour and spells are configured here rather than
+/// in the spawning or AI code.
+
+use bevy::prelude::*;
+use rand::Rng;
+use crate::resources::rng::GameRng;
+use crate::components::{DamageType, Resistance, MonsterBehaviour, Spell, SpellKind, Faction, BossPhase};
+use crate::constants::*;
+
+// ============================================================================
+// MONSTER TEMPLATE
+// ============================================================================
+
+/// Everything needed to spawn one kind of monster
+#[derive(Debug, Clone)]
+pub struct MonsterTemplate {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub color: Color,
+    /// Tileset index
+    pub tile: usize,
+    /// Character drawn in glyph mode
+    pub glyph: char,
+    pub faction: Faction,
+    pub health: i32,
+    pub power: i32,
+    pub defense: i32,
+    pub accuracy: i32,
+    pub evasion: i32,
+    pub fov_radius: i32,
+    pub weapon: (&'static str, DamageType),
+    pub resistances: &'static [(DamageType, Resistance)],
+    pub behaviour: MonsterBehaviour,
+    pub spells: &'static [Spell],
+    /// Phases for boss monsters (empty for everything else)
+    pub boss_phases: &'static [BossPhase],
+    /// Relative chance of appearing during level generation (0 = summon only)
+    pub spawn_weight: u32,
+}
+
+// ============================================================================
+// BESTIARY RESOURCE
+// ============================================================================
+
+/// All monster templates known to the game
+#[derive(Resource)]
+pub struct Bestiary {
+    pub templates: Vec<MonsterTemplate>,
+}
+
+impl Default for Bestiary {
+    fn default() -> Self {
+        Self {
+            templates: default_templates(),
+        }
+    }
+}
+
+impl Bestiary {
+    /// Look up a template by id
+    pub fn get(&self, id: &str) -> Option<&MonsterTemplate> {
+        self.templates.iter().find(|template| template.id == id)
+    }
+
+    /// Pick a random template weighted by spawn_weight
+    pub fn random_spawnable(&self, rng: &mut GameRng) -> Option<&MonsterTemplate> {
+        let total: u32 = self.templates.iter().map(|t| t.spawn_weight).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut roll = rng.gen_range(0..total);
+        for template in &self.templates {
+            if roll < template.spawn_weight {
+                return Some(template);
+            }
+            roll -= template.spawn_weight;
+        }
+        None
+    }
+}
+
+/// The built-in monster roster
+fn default_templates() -> Vec<MonsterTemplate> {
+    vec![
+        MonsterTemplate {
+            id: "goblin",
+            name: "Goblin",
+            color: COLOR_ENEMY,
+            tile: TILE_GOBLIN,
+            glyph: 'g',
+            faction: Faction::Goblins,
+            health: ENEMY_STARTING_HEALTH,
+            power: ENEMY_ATTACK_POWER,
+            defense: ENEMY_DEFENSE,
+            accuracy: ENEMY_ACCURACY,
+            evasion: ENEMY_EVASION,
+            fov_radius: ENEMY_FOV_RADIUS,
+            weapon: ("Rusty Dagger", DamageType::Physical),
+            // Goblins are hardy against poison but fear fire
+            resistances: &[
+                (DamageType::Poison, Resistance::Resistant),
+                (DamageType::Fire, Resistance::Vulnerable),
+            ],
+            behaviour: MonsterBehaviour::Melee,
+            spells: &[],
+            boss_phases: &[],
+            spawn_weight: 6,
+        },
+        MonsterTemplate {
+            id: "goblin_archer",
+            name: "Goblin Archer",
+            color: COLOR_GOBLIN_ARCHER,
+            tile: TILE_GOBLIN_ARCHER,
+            glyph: 'g',
+            faction: Faction::Goblins,
+            health: 20,
+            power: 7,
+            defense: 0,
+            accuracy: 7,
+            evasion: 5,
+            fov_radius: 8,
+            weapon: ("Shortbow", DamageType::Physical),
+            resistances: &[
+                (DamageType::Poison, Resistance::Resistant),
+                (DamageType::Fire, Resistance::Vulnerable),
+            ],
+            behaviour: MonsterBehaviour::Archer { range: 6, preferred_distance: 3 },
+            spells: &[],
+            boss_phases: &[],
+            spawn_weight: 3,
+        },
+        MonsterTemplate {
+            id: "goblin_shaman",
+            name: "Goblin Shaman",
+            color: COLOR_GOBLIN_SHAMAN,
+            tile: TILE_GOBLIN_SHAMAN,
+            glyph: 'g',
+            faction: Faction::Goblins,
+            health: 22,
+            power: 5,
+            defense: 0,
+            accuracy: 6,
+            evasion: 4,
+            fov_radius: 7,
+            weapon: ("Gnarled Staff", DamageType::Physical),
+            resistances: &[(DamageType::Fire, Resistance::Resistant)],
+            behaviour: MonsterBehaviour::Caster,
+            spells: &[
+                Spell {
+                    name: "fire bolt",
+                    kind: SpellKind::Bolt { damage_type: DamageType::Fire },
+                    range: 6,
+                    cooldown: 3,
+                },
+                Spell {
+                    name: "mend",
+                    kind: SpellKind::HealAlly { amount: 10 },
+                    range: 6,
+                    cooldown: 5,
+                },
+                Spell {
+                    name: "blink",
+                    kind: SpellKind::Blink,
+                    range: 5,
+                    cooldown: 8,
+                },
+            ],
+            boss_phases: &[],
+            spawn_weight: 2,
+        },
+        MonsterTemplate {
+            id: "necromancer",
+            name: "Necromancer",
+            color: COLOR_NECROMANCER,
+            tile: TILE_NECROMANCER,
+            glyph: 'p',
+            faction: Faction::Undead,
+            health: 26,
+            power: 5,
+            defense: 1,
+            accuracy: 6,
+            evasion: 4,
+            fov_radius: 8,
+            weapon: ("Bone Wand", DamageType::Cold),
+            resistances: &[
+                (DamageType::Cold, Resistance::Resistant),
+                (DamageType::Poison, Resistance::Immune),
+            ],
+            behaviour: MonsterBehaviour::Summoner { preferred_distance: 4 },
+            spells: &[Spell {
+                name: "raise skeleton",
+                kind: SpellKind::Summon { minion: "skeleton", max_minions: 3 },
+                range: 1,
+                cooldown: 6,
+            }],
+            boss_phases: &[],
+            spawn_weight: 1,
+        },
+        MonsterTemplate {
+            id: "skeleton",
+            name: "Skeleton",
+            color: COLOR_SKELETON,
+            tile: TILE_SKELETON,
+            glyph: 's',
+            faction: Faction::Undead,
+            health: 15,
+            power: 6,
+            defense: 2,
+            accuracy: 4,
+            evasion: 3,
+            fov_radius: 6,
+            weapon: ("Bony Claws", DamageType::Physical),
+            resistances: &[
+                (DamageType::Poison, Resistance::Immune),
+                (DamageType::Cold, Resistance::Resistant),
+                (DamageType::Fire, Resistance::Vulnerable),
+            ],
+            behaviour: MonsterBehaviour::Melee,
+            spells: &[],
+            boss_phases: &[],
+            spawn_weight: 0,
+        },
+        MonsterTemplate {
+            id: "giant_beetle",
+            name: "Giant Beetle",
+            color: COLOR_GIANT_BEETLE,
+            tile: TILE_GIANT_BEETLE,
+            glyph: 'a',
+            // Ignores everyone until attacked
+            faction: Faction::Wildlife,
+            health: 18,
+            power: 6,
+            defense: 3,
+            accuracy: 4,
+            evasion: 2,
+            fov_radius: 4,
+            weapon: ("Mandibles", DamageType::Physical),
+            resistances: &[(DamageType::Poison, Resistance::Resistant)],
+            behaviour: MonsterBehaviour::Melee,
+            spells: &[],
+            boss_phases: &[],
+            spawn_weight: 2,
+        },
+        MonsterTemplate {
+            id: "lich",
+            name: "The Lich",
+            color: COLOR_LICH,
+            tile: TILE_LICH,
+            glyph: 'L',
+            faction: Faction::Undead,
+            health: 90,
+            power: 10,
+            defense: 3,
+            accuracy: 8,
+            evasion: 5,
+            fov_radius: 8,
+            weapon: ("Soulreaver", DamageType::Cold),
+            resistances: &[
+                (DamageType::Cold, Resistance::Immune),
+                (DamageType::Poison, Resistance::Immune),
+                (DamageType::Fire, Resistance::Vulnerable),
+            ],
+            behaviour: MonsterBehaviour::Caster,
+            spells: &[FROST_BOLT],
+            boss_phases: &[
+                BossPhase {
+                    below_health: 0.66,
+                    announcement: "The Lich calls its servants from the grave!",
+                    spells: &[
+                        Spell {
+                            name: "raise dead",
+                            kind: SpellKind::Summon { minion: "skeleton", max_minions: 4 },
+                            range: 1,
+                            cooldown: 4,
+                        },
+                        FROST_BOLT,
+                    ],
+                    power_bonus: 0,
+                },
+                BossPhase {
+                    below_health: 0.33,
+                    announcement: "The Lich's bones blaze with a cold fire!",
+                    spells: &[
+                        Spell {
+                            name: "frost nova",
+                            kind: SpellKind::Nova { damage_type: DamageType::Cold, radius: 2 },
+                            range: 2,
+                            cooldown: 3,
+                        },
+                        FROST_BOLT,
+                        Spell {
+                            name: "blink",
+                            kind: SpellKind::Blink,
+                            range: 5,
+                            cooldown: 6,
+                        },
+                    ],
+                    power_bonus: 4,
+                },
+            ],
+            // Only ever placed in the arena vault
+            spawn_weight: 0,
+        },
+    ]
+}
+
+/// The Lich's bread-and-butter attack, kept through every phase
+const FROST_BOLT: Spell = Spell {
+    name: "frost bolt",
+    kind: SpellKind::Bolt { damage_type: DamageType::Cold },
+    range: 6,
+    cooldown: 2,
+};
@@ -1,53 +1,147 @@
 /// Combat log for tracking combat messages
 
 use bevy::prelude::*;
-use std::collections::VecDeque;
+use crate::constants::{
+    COMBAT_LOG_HISTORY_LIMIT,
+    COLOR_LOG_INFO, COLOR_LOG_MISS, COLOR_LOG_DAMAGE, COLOR_LOG_KILL, COLOR_LOG_HEAL,
+};
+
+// ============================================================================
+// LOG ENTRIES
+// ============================================================================
+
+/// Category of a log entry, used for coloring and filtering
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogEntryKind {
+    /// General messages (traps, discoveries)
+    Info,
+    /// An attack that missed
+    Miss,
+    /// Damage dealt to an entity
+    Damage,
+    /// An entity died
+    Kill,
+    /// Health restored
+    Heal,
+}
+
+impl LogEntryKind {
+    /// Text color for this kind of entry
+    pub fn color(&self) -> Color {
+        match self {
+            LogEntryKind::Info => COLOR_LOG_INFO,
+            LogEntryKind::Miss => COLOR_LOG_MISS,
+            LogEntryKind::Damage => COLOR_LOG_DAMAGE,
+            LogEntryKind::Kill => COLOR_LOG_KILL,
+            LogEntryKind::Heal => COLOR_LOG_HEAL,
+        }
+    }
+}
+
+/// A single typed line in the combat log
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub kind: LogEntryKind,
+    pub text: String,
+}
 
 // ============================================================================
 // COMBAT LOG RESOURCE
 // ============================================================================
 
-/// Stores combat messages for player feedback
+/// Stores the full history of combat messages for player feedback
+///
+/// Entries are built from combat events by `record_combat_events_system`.
+/// `scroll` counts how many entries back from the newest the view is.
 #[derive(Resource)]
 pub struct CombatLog {
-    messages: VecDeque<String>,
-    max_messages: usize,
+    entries: Vec<LogEntry>,
+    max_entries: usize,
+    scroll: usize,
 }
 
 impl Default for CombatLog {
     fn default() -> Self {
-        Self::new(10)
+        Self::new(COMBAT_LOG_HISTORY_LIMIT)
     }
 }
 
 impl CombatLog {
-    pub fn new(max_messages: usize) -> Self {
+    pub fn new(max_entries: usize) -> Self {
         Self {
-            messages: VecDeque::with_capacity(max_messages),
-            max_messages,
+            entries: Vec::new(),
+            max_entries,
+            scroll: 0,
         }
     }
 
-    /// Add a message to the log
-    pub fn add_message(&mut self, message: String) {
-        info!("COMBAT LOG: {}", message);
+    /// Add a typed entry to the log
+    pub fn add_entry(&mut self, kind: LogEntryKind, text: String) {
+        info!("COMBAT LOG: {}", text);
 
-        // Add message
-        self.messages.push_back(message);
+        self.entries.push(LogEntry { kind, text });
 
-        // Remove oldest if over capacity
-        while self.messages.len() > self.max_messages {
-            self.messages.pop_front();
+        // Drop oldest entries beyond the history limit
+        if self.entries.len() > self.max_entries {
+            let excess = self.entries.len() - self.max_entries;
+            self.entries.drain(..excess);
+        }
+
+        // Keep a scrolled-back view anchored on the same entries
+        if self.scroll > 0 {
+            self.scroll = (self.scroll + 1).min(self.max_scroll());
         }
     }
 
-    /// Get all messages (most recent last)
+    /// Add a plain informational message to the log
+    pub fn add_message(&mut self, message: String) {
+        self.add_entry(LogEntryKind::Info, message);
+    }
+
+    /// Get all entries (most recent last)
+    pub fn entries(&self) -> &[LogEntry] {
+        &self.entries
+    }
+
+    /// Get all message texts (most recent last)
     pub fn get_messages(&self) -> Vec<&String> {
-        self.messages.iter().collect()
+        self.entries.iter().map(|entry| &entry.text).collect()
+    }
+
+    /// Entries shown in a view of `lines` rows at the current scroll position
+    pub fn visible_entries(&self, lines: usize) -> &[LogEntry] {
+        let end = self.entries.len() - self.scroll.min(self.entries.len());
+        let start = end.saturating_sub(lines);
+        &self.entries[start..end]
+    }
+
+    /// How many entries back from the newest the view is scrolled
+    pub fn scroll_offset(&self) -> usize {
+        self.scroll
+    }
+
+    /// Scroll towards older entries
+    pub fn scroll_up(&mut self, amount: usize) {
+        self.scroll = (self.scroll + amount).min(self.max_scroll());
+    }
+
+    /// Scroll towards newer entries
+    pub fn scroll_down(&mut self, amount: usize) {
+        self.scroll = self.scroll.saturating_sub(amount);
+    }
+
+    /// Jump back to the newest entries
+    pub fn scroll_to_latest(&mut self) {
+        self.scroll = 0;
     }
 
     /// Clear all messages
     pub fn clear(&mut self) {
-        self.messages.clear();
+        self.entries.clear();
+        self.scroll = 0;
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.entries.len().saturating_sub(1)
     }
 }
@@ -0,0 +1,13 @@
+/// Dungeon depth tracking
+
+use bevy::prelude::*;
+
+/// How deep the player is (1 = the first level)
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Depth(pub u32);
+
+impl Default for Depth {
+    fn default() -> Self {
+        Self(1)
+    }
+}
@@ -0,0 +1,369 @@
+/// Dijkstra maps for shared monster movement and auto-explore
+///
+/// A Dijkstra map stores, for every tile, the cost of the cheapest path to
+/// the nearest goal. Walking "downhill" approaches the goals; an inverted and
+/// rescanned map walks away from them while still avoiding dead ends.
+
+use bevy::prelude::*;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use crate::resources::map::{CurrentMap, Position};
+use crate::resources::visibility::{VisibilityMap, VisibilityState};
+use crate::constants::FLEE_MAP_COEFFICIENT;
+
+/// Value of tiles no goal can be reached from
+pub const UNREACHABLE: i32 = i32::MAX;
+
+/// The eight neighbouring offsets (orthogonal first)
+pub const NEIGHBOURS: [(i32, i32); 8] = [
+    (0, 1), (0, -1), (-1, 0), (1, 0),
+    (-1, 1), (1, 1), (-1, -1), (1, -1),
+];
+
+// ============================================================================
+// DIJKSTRA MAP
+// ============================================================================
+
+/// Cost-to-goal values for every tile of a map
+#[derive(Debug, Clone)]
+pub struct DijkstraMap {
+    pub width: usize,
+    pub height: usize,
+    pub values: Vec<i32>,
+}
+
+impl DijkstraMap {
+    /// Create a map with every tile unreachable
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            values: vec![UNREACHABLE; width * height],
+        }
+    }
+
+    /// Build a map towards `goals` over walkable tiles, using terrain costs
+    pub fn build(map: &CurrentMap, goals: &[Position]) -> Self {
+        Self::build_with(map, goals, |_| true)
+    }
+
+    /// Build a map towards `goals`, only crossing tiles accepted by `passable`
+    /// (in addition to being walkable)
+    pub fn build_with(
+        map: &CurrentMap,
+        goals: &[Position],
+        passable: impl Fn(Position) -> bool,
+    ) -> Self {
+        let mut dijkstra = Self::seeded(map, goals);
+        dijkstra.rescan(map, passable, UNREACHABLE);
+        dijkstra
+    }
+
+    /// Build a map towards `goals` that stops at a cost of `max_cost`
+    ///
+    /// Tiles further away stay unreachable, so a short search over a big map
+    /// only visits the tiles near the goals.
+    pub fn build_within(map: &CurrentMap, goals: &[Position], max_cost: i32) -> Self {
+        let mut dijkstra = Self::seeded(map, goals);
+        dijkstra.rescan(map, |_| true, max_cost);
+        dijkstra
+    }
+
+    /// Build a map that leads away from the goals of `self`
+    ///
+    /// Values are multiplied by -FLEE_MAP_COEFFICIENT and rescanned, so
+    /// fleeing prefers open areas over corners near the threat.
+    pub fn flee_map(&self, map: &CurrentMap) -> Self {
+        let mut flee = self.clone();
+        for value in flee.values.iter_mut() {
+            if *value != UNREACHABLE {
+                *value = -((*value as f32 * FLEE_MAP_COEFFICIENT).round() as i32);
+            }
+        }
+        flee.rescan(map, |_| true, UNREACHABLE);
+        flee
+    }
+
+    /// Build a map towards every known walkable tile that borders unseen space
+    ///
+    /// Only explored tiles are crossed, so the route never relies on
+    /// terrain the player hasn't seen. `blocked` tiles (such as known traps)
+    /// are neither crossed nor explored to.
+    pub fn explore_map(
+        map: &CurrentMap,
+        visibility: &VisibilityMap,
+        blocked: impl Fn(Position) -> bool,
+    ) -> Self {
+        let known = |pos: Position| visibility.get(&pos) != VisibilityState::Unseen;
+
+        let frontier: Vec<Position> = (0..map.height as i32)
+            .flat_map(|y| (0..map.width as i32).map(move |x| Position::new(x, y)))
+            .filter(|pos| known(*pos) && map.is_walkable(pos.x, pos.y) && !blocked(*pos))
+            .filter(|pos| {
+                NEIGHBOURS.iter().any(|(dx, dy)| {
+                    let neighbour = Position::new(pos.x + dx, pos.y + dy);
+                    map.get_tile(neighbour.x, neighbour.y).is_some() && !known(neighbour)
+                })
+            })
+            .collect();
+
+        Self::build_with(map, &frontier, |pos| known(pos) && !blocked(pos))
+    }
+
+    /// Value at a position (None if out of bounds or unreachable)
+    pub fn get(&self, pos: Position) -> Option<i32> {
+        self.index(pos)
+            .map(|idx| self.values[idx])
+            .filter(|value| *value != UNREACHABLE)
+    }
+
+    /// Neighbouring walkable tile with the lowest value below `from`'s
+    ///
+    /// `blocked` lets callers rule out occupied tiles.
+    pub fn downhill_step(
+        &self,
+        map: &CurrentMap,
+        from: Position,
+        blocked: impl Fn(Position) -> bool,
+    ) -> Option<Position> {
+        let current = self.get(from).unwrap_or(UNREACHABLE);
+
+        NEIGHBOURS
+            .iter()
+            .map(|(dx, dy)| Position::new(from.x + dx, from.y + dy))
+            .filter(|pos| map.is_walkable(pos.x, pos.y) && !blocked(*pos))
+            .filter_map(|pos| self.get(pos).map(|value| (value, pos)))
+            .filter(|(value, _)| *value < current)
+            .min_by_key(|(value, _)| *value)
+            .map(|(_, pos)| pos)
+    }
+
+    /// Nearest goal tile reachable from `from`, following the map downhill
+    pub fn nearest_goal(&self, map: &CurrentMap, from: Position) -> Option<Position> {
+        let mut pos = from;
+        self.get(pos)?;
+
+        while self.get(pos) != Some(0) {
+            pos = self.downhill_step(map, pos, |_| false)?;
+        }
+        Some(pos)
+    }
+
+    /// Tiles walked from `from` down to the nearest goal (excluding `from`)
+    pub fn path_to_goal(&self, map: &CurrentMap, from: Position) -> Option<Vec<Position>> {
+        let mut path = Vec::new();
+        let mut pos = from;
+        self.get(pos)?;
+
+        while self.get(pos) != Some(0) {
+            pos = self.downhill_step(map, pos, |_| false)?;
+            path.push(pos);
+        }
+        Some(path)
+    }
+
+    /// Nearest reachable known tile bordering unseen space, avoiding `blocked` tiles
+    pub fn nearest_unexplored(
+        map: &CurrentMap,
+        visibility: &VisibilityMap,
+        from: Position,
+        blocked: impl Fn(Position) -> bool,
+    ) -> Option<Position> {
+        Self::explore_map(map, visibility, blocked).nearest_goal(map, from)
+    }
+
+    /// Relax values outward until every tile holds its cheapest cost (up to
+    /// `max_value`; costlier tiles are left as they were)
+    fn rescan(&mut self, map: &CurrentMap, passable: impl Fn(Position) -> bool, max_value: i32) {
+        let mut open: BinaryHeap<Reverse<(i32, usize)>> = self
+            .values
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| **value != UNREACHABLE)
+            .map(|(idx, value)| Reverse((*value, idx)))
+            .collect();
+
+        while let Some(Reverse((value, idx))) = open.pop() {
+            if value > self.values[idx] {
+                continue; // Stale entry
+            }
+
+            let pos = self.position(idx);
+            for (dx, dy) in NEIGHBOURS {
+                let next = Position::new(pos.x + dx, pos.y + dy);
+                let Some(cost) = map.movement_cost(next.x, next.y) else {
+                    continue;
+                };
+                if !passable(next) {
+                    continue;
+                }
+
+                let next_idx = self.index(next).expect("walkable tiles are in bounds");
+                let next_value = value + cost;
+                if next_value <= max_value && next_value < self.values[next_idx] {
+                    self.values[next_idx] = next_value;
+                    open.push(Reverse((next_value, next_idx)));
+                }
+            }
+        }
+    }
+
+    /// Unscanned map with only the goals set (to 0)
+    fn seeded(map: &CurrentMap, goals: &[Position]) -> Self {
+        let mut dijkstra = Self::new(map.width, map.height);
+        for goal in goals {
+            if let Some(idx) = dijkstra.index(*goal) {
+                dijkstra.values[idx] = 0;
+            }
+        }
+        dijkstra
+    }
+
+    fn index(&self, pos: Position) -> Option<usize> {
+        if pos.x < 0 || pos.y < 0 || pos.x as usize >= self.width || pos.y as usize >= self.height {
+            return None;
+        }
+        Some(pos.y as usize * self.width + pos.x as usize)
+    }
+
+    fn position(&self, idx: usize) -> Position {
+        Position::new((idx % self.width) as i32, (idx / self.width) as i32)
+    }
+}
+
+// ============================================================================
+// SHARED DIJKSTRA MAPS RESOURCE
+// ============================================================================
+
+/// Dijkstra maps shared by every monster
+///
+/// The approach and flee maps are rebuilt when the player moves; maps towards
+/// other goals are built on demand and dropped at the start of each enemy turn.
+#[derive(Resource, Default)]
+pub struct DijkstraMaps {
+    /// Leads towards the player
+    pub approach: Option<DijkstraMap>,
+
+    /// Leads away from the player
+    pub flee: Option<DijkstraMap>,
+
+    /// Maps towards single goal tiles, built this turn
+    pub goals: HashMap<Position, DijkstraMap>,
+}
+
+impl DijkstraMaps {
+    /// Map towards `goal`, built the first time it is asked for this turn
+    pub fn towards(&mut self, map: &CurrentMap, goal: Position) -> &DijkstraMap {
+        self.goals
+            .entry(goal)
+            .or_insert_with(|| DijkstraMap::build(map, &[goal]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::map::{TileType, grid_distance};
+
+    /// 7x5 map: a 3x3 room (x 1-3) and, behind a wall, a 1x3 pocket (x 5)
+    fn two_rooms() -> CurrentMap {
+        let mut map = CurrentMap::new(7, 5);
+        for y in 1..=3 {
+            for x in [1, 2, 3, 5] {
+                map.tiles[y][x] = TileType::Floor;
+            }
+        }
+        map
+    }
+
+    #[test]
+    fn approach_values_grow_with_distance() {
+        let map = two_rooms();
+        let approach = DijkstraMap::build(&map, &[Position::new(1, 2)]);
+
+        assert_eq!(approach.get(Position::new(1, 2)), Some(0));
+        assert_eq!(approach.get(Position::new(2, 2)), Some(1));
+        assert_eq!(approach.get(Position::new(3, 2)), Some(2));
+        assert_eq!(approach.get(Position::new(3, 3)), Some(2));
+    }
+
+    #[test]
+    fn walls_and_cut_off_tiles_are_unreachable() {
+        let map = two_rooms();
+        let approach = DijkstraMap::build(&map, &[Position::new(1, 2)]);
+
+        assert_eq!(approach.get(Position::new(0, 0)), None);
+        assert_eq!(approach.get(Position::new(4, 2)), None);
+        assert_eq!(approach.get(Position::new(5, 2)), None);
+        assert_eq!(approach.get(Position::new(-1, 2)), None);
+    }
+
+    #[test]
+    fn flee_map_leads_away_from_the_goal() {
+        let map = two_rooms();
+        let goal = Position::new(1, 2);
+        let flee = DijkstraMap::build(&map, &[goal]).flee_map(&map);
+
+        let from = Position::new(2, 2);
+        let step = flee.downhill_step(&map, from, |_| false).expect("room to flee");
+        assert!(grid_distance(step, goal) > grid_distance(from, goal));
+    }
+
+    #[test]
+    fn explore_map_targets_only_the_frontier() {
+        let map = two_rooms();
+        let mut visibility = VisibilityMap::new();
+        for y in 0..5 {
+            for x in 0..=2 {
+                visibility.mark_explored(Position::new(x, y));
+            }
+        }
+        let explore = DijkstraMap::explore_map(&map, &visibility, |_| false);
+
+        // Known floor next to the unseen column is the frontier
+        for y in 1..=3 {
+            assert_eq!(explore.get(Position::new(2, y)), Some(0));
+        }
+        // Known floor further in leads there, but isn't a goal itself
+        assert_eq!(explore.get(Position::new(1, 2)), Some(1));
+        // Unseen floor is neither a goal nor crossed
+        assert_eq!(explore.get(Position::new(3, 2)), None);
+    }
+
+    #[test]
+    fn explore_map_avoids_blocked_tiles() {
+        let map = two_rooms();
+        let mut visibility = VisibilityMap::new();
+        for y in 0..5 {
+            for x in 0..=2 {
+                visibility.mark_explored(Position::new(x, y));
+            }
+        }
+        let trap = Position::new(2, 2);
+        let explore = DijkstraMap::explore_map(&map, &visibility, |pos| pos == trap);
+
+        assert_eq!(explore.get(trap), None);
+        assert_eq!(explore.get(Position::new(2, 1)), Some(0));
+        let step = explore.downhill_step(&map, Position::new(1, 2), |_| false);
+        assert!(step.is_some_and(|step| step != trap));
+    }
+
+    #[test]
+    fn path_to_goal_walks_down_to_the_goal() {
+        let map = two_rooms();
+        let goal = Position::new(1, 2);
+        let approach = DijkstraMap::build(&map, &[goal]);
+
+        let path = approach.path_to_goal(&map, Position::new(3, 2)).expect("goal is reachable");
+        assert_eq!(path.len(), 2);
+        assert_eq!(path.last(), Some(&goal));
+    }
+
+    #[test]
+    fn path_to_goal_is_none_for_unreachable_goals() {
+        let map = two_rooms();
+        let approach = DijkstraMap::build(&map, &[Position::new(5, 2)]);
+
+        assert_eq!(approach.path_to_goal(&map, Position::new(1, 2)), None);
+    }
+}
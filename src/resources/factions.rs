@@ -0,0 +1,89 @@
+/// Faction relationship table
+///
+/// Targeting in combat and AI asks this table whether two actors are hostile
+/// rather than looking at `Player`/`Enemy` markers.
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::components::{Faction, Reaction, Grudges};
+
+// ============================================================================
+// ALLEGIANCE
+// ============================================================================
+
+/// An actor's faction and grudges, as needed for hostility checks
+#[derive(Debug, Clone, Copy)]
+pub struct Allegiance<'a> {
+    pub entity: Entity,
+    pub faction: Faction,
+    pub grudges: Option<&'a Grudges>,
+}
+
+impl<'a> Allegiance<'a> {
+    pub fn new(entity: Entity, faction: &Faction, grudges: Option<&'a Grudges>) -> Self {
+        Self {
+            entity,
+            faction: *faction,
+            grudges,
+        }
+    }
+
+    fn holds_grudge_against(&self, other: Entity) -> bool {
+        self.grudges.is_some_and(|grudges| grudges.holds_against(other))
+    }
+}
+
+// ============================================================================
+// FACTION TABLE RESOURCE
+// ============================================================================
+
+/// Symmetric reactions between factions
+///
+/// Members of the same faction are always friendly; unlisted pairs are neutral.
+#[derive(Resource, Debug, Clone)]
+pub struct FactionTable {
+    relations: HashMap<(Faction, Faction), Reaction>,
+}
+
+impl Default for FactionTable {
+    fn default() -> Self {
+        let mut table = Self {
+            relations: HashMap::new(),
+        };
+        table.set(Faction::Player, Faction::Goblins, Reaction::Hostile);
+        table.set(Faction::Player, Faction::Undead, Reaction::Hostile);
+        table.set(Faction::Goblins, Faction::Undead, Reaction::Hostile);
+        table
+    }
+}
+
+impl FactionTable {
+    /// Set how two factions treat each other (both ways)
+    pub fn set(&mut self, a: Faction, b: Faction, reaction: Reaction) {
+        self.relations.insert((a, b), reaction);
+        self.relations.insert((b, a), reaction);
+    }
+
+    /// How faction `a` treats faction `b`
+    pub fn reaction(&self, a: Faction, b: Faction) -> Reaction {
+        if a == b {
+            return Reaction::Friendly;
+        }
+        self.relations.get(&(a, b)).copied().unwrap_or(Reaction::Neutral)
+    }
+
+    /// Whether two actors will fight: hostile factions, or a grudge either way
+    pub fn is_hostile(&self, a: Allegiance, b: Allegiance) -> bool {
+        if a.entity == b.entity {
+            return false;
+        }
+        self.reaction(a.faction, b.faction) == Reaction::Hostile
+            || a.holds_grudge_against(b.entity)
+            || b.holds_grudge_against(a.entity)
+    }
+
+    /// Whether two actors are on the same side and at peace
+    pub fn is_friendly(&self, a: Allegiance, b: Allegiance) -> bool {
+        self.reaction(a.faction, b.faction) == Reaction::Friendly && !self.is_hostile(a, b)
+    }
+}
@@ -0,0 +1,506 @@
+/// Keybindings - mapping physical keys to player actions
+///
+/// Gameplay systems never look at `KeyCode`s directly; they ask `ActionInput`
+/// which actions were triggered this frame. Keys are translated through the
+/// `KeyBindings` table, built from default layouts and an optional user file.
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::fmt;
+
+// ============================================================================
+// PLAYER ACTIONS
+// ============================================================================
+
+/// Everything the player can ask their character to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlayerAction {
+    MoveNorth,
+    MoveSouth,
+    MoveEast,
+    MoveWest,
+    MoveNorthEast,
+    MoveNorthWest,
+    MoveSouthEast,
+    MoveSouthWest,
+    Wait,
+    Attack,
+    Disarm,
+    Descend,
+    ToggleSneak,
+    CommandPet,
+    AutoExplore,
+    Rest,
+    Look,
+}
+
+impl PlayerAction {
+    pub const ALL: [PlayerAction; 17] = [
+        PlayerAction::MoveNorth,
+        PlayerAction::MoveSouth,
+        PlayerAction::MoveEast,
+        PlayerAction::MoveWest,
+        PlayerAction::MoveNorthEast,
+        PlayerAction::MoveNorthWest,
+        PlayerAction::MoveSouthEast,
+        PlayerAction::MoveSouthWest,
+        PlayerAction::Wait,
+        PlayerAction::Attack,
+        PlayerAction::Disarm,
+        PlayerAction::Descend,
+        PlayerAction::ToggleSneak,
+        PlayerAction::CommandPet,
+        PlayerAction::AutoExplore,
+        PlayerAction::Rest,
+        PlayerAction::Look,
+    ];
+
+    /// Name used in the bindings file
+    pub fn name(self) -> &'static str {
+        match self {
+            PlayerAction::MoveNorth => "move_north",
+            PlayerAction::MoveSouth => "move_south",
+            PlayerAction::MoveEast => "move_east",
+            PlayerAction::MoveWest => "move_west",
+            PlayerAction::MoveNorthEast => "move_north_east",
+            PlayerAction::MoveNorthWest => "move_north_west",
+            PlayerAction::MoveSouthEast => "move_south_east",
+            PlayerAction::MoveSouthWest => "move_south_west",
+            PlayerAction::Wait => "wait",
+            PlayerAction::Attack => "attack",
+            PlayerAction::Disarm => "disarm",
+            PlayerAction::Descend => "descend",
+            PlayerAction::ToggleSneak => "toggle_sneak",
+            PlayerAction::CommandPet => "command_pet",
+            PlayerAction::AutoExplore => "auto_explore",
+            PlayerAction::Rest => "rest",
+            PlayerAction::Look => "look",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.name() == name)
+    }
+
+    /// Grid step for movement actions (+y is north)
+    pub fn direction(self) -> Option<(i32, i32)> {
+        match self {
+            PlayerAction::MoveNorth => Some((0, 1)),
+            PlayerAction::MoveSouth => Some((0, -1)),
+            PlayerAction::MoveEast => Some((1, 0)),
+            PlayerAction::MoveWest => Some((-1, 0)),
+            PlayerAction::MoveNorthEast => Some((1, 1)),
+            PlayerAction::MoveNorthWest => Some((-1, 1)),
+            PlayerAction::MoveSouthEast => Some((1, -1)),
+            PlayerAction::MoveSouthWest => Some((-1, -1)),
+            _ => None,
+        }
+    }
+
+    /// Movement action for a grid step (None for no step or more than one tile)
+    pub fn for_direction(dx: i32, dy: i32) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.direction() == Some((dx, dy)))
+    }
+}
+
+// ============================================================================
+// DEFAULT LAYOUTS
+// ============================================================================
+
+/// Action keys shared by every layout
+pub const ACTION_BINDINGS: &[(KeyCode, PlayerAction)] = &[
+    (KeyCode::Space, PlayerAction::Attack),
+    (KeyCode::KeyX, PlayerAction::Disarm),
+    (KeyCode::Period, PlayerAction::Descend),
+    (KeyCode::KeyC, PlayerAction::ToggleSneak),
+    (KeyCode::KeyV, PlayerAction::CommandPet),
+    (KeyCode::KeyO, PlayerAction::AutoExplore),
+    (KeyCode::KeyR, PlayerAction::Rest),
+    (KeyCode::KeyZ, PlayerAction::Wait),
+    (KeyCode::Semicolon, PlayerAction::Look),
+];
+
+/// Built-in sets of movement bindings that can be combined
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyLayout {
+    Wasd,
+    Arrows,
+    /// Numpad movement in 8 directions, 5 waits
+    Numpad,
+    /// hjkl + yubn movement
+    Vi,
+}
+
+impl KeyLayout {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "wasd" => Some(KeyLayout::Wasd),
+            "arrows" => Some(KeyLayout::Arrows),
+            "numpad" => Some(KeyLayout::Numpad),
+            "vi" => Some(KeyLayout::Vi),
+            _ => None,
+        }
+    }
+
+    pub fn bindings(self) -> &'static [(KeyCode, PlayerAction)] {
+        match self {
+            KeyLayout::Wasd => &[
+                (KeyCode::KeyW, PlayerAction::MoveNorth),
+                (KeyCode::KeyS, PlayerAction::MoveSouth),
+                (KeyCode::KeyD, PlayerAction::MoveEast),
+                (KeyCode::KeyA, PlayerAction::MoveWest),
+            ],
+            KeyLayout::Arrows => &[
+                (KeyCode::ArrowUp, PlayerAction::MoveNorth),
+                (KeyCode::ArrowDown, PlayerAction::MoveSouth),
+                (KeyCode::ArrowRight, PlayerAction::MoveEast),
+                (KeyCode::ArrowLeft, PlayerAction::MoveWest),
+            ],
+            KeyLayout::Numpad => &[
+                (KeyCode::Numpad8, PlayerAction::MoveNorth),
+                (KeyCode::Numpad2, PlayerAction::MoveSouth),
+                (KeyCode::Numpad6, PlayerAction::MoveEast),
+                (KeyCode::Numpad4, PlayerAction::MoveWest),
+                (KeyCode::Numpad9, PlayerAction::MoveNorthEast),
+                (KeyCode::Numpad7, PlayerAction::MoveNorthWest),
+                (KeyCode::Numpad3, PlayerAction::MoveSouthEast),
+                (KeyCode::Numpad1, PlayerAction::MoveSouthWest),
+                (KeyCode::Numpad5, PlayerAction::Wait),
+            ],
+            KeyLayout::Vi => &[
+                (KeyCode::KeyK, PlayerAction::MoveNorth),
+                (KeyCode::KeyJ, PlayerAction::MoveSouth),
+                (KeyCode::KeyL, PlayerAction::MoveEast),
+                (KeyCode::KeyH, PlayerAction::MoveWest),
+                (KeyCode::KeyU, PlayerAction::MoveNorthEast),
+                (KeyCode::KeyY, PlayerAction::MoveNorthWest),
+                (KeyCode::KeyN, PlayerAction::MoveSouthEast),
+                (KeyCode::KeyB, PlayerAction::MoveSouthWest),
+            ],
+        }
+    }
+}
+
+/// Layouts used when the bindings file doesn't pick any
+pub const DEFAULT_LAYOUTS: [KeyLayout; 3] = [KeyLayout::Wasd, KeyLayout::Arrows, KeyLayout::Numpad];
+
+/// Keys that can be named in the bindings file (by their `KeyCode` name)
+const NAMEABLE_KEYS: &[KeyCode] = &[
+    KeyCode::KeyA, KeyCode::KeyB, KeyCode::KeyC, KeyCode::KeyD, KeyCode::KeyE, KeyCode::KeyF,
+    KeyCode::KeyG, KeyCode::KeyH, KeyCode::KeyI, KeyCode::KeyJ, KeyCode::KeyK, KeyCode::KeyL,
+    KeyCode::KeyM, KeyCode::KeyN, KeyCode::KeyO, KeyCode::KeyP, KeyCode::KeyQ, KeyCode::KeyR,
+    KeyCode::KeyS, KeyCode::KeyT, KeyCode::KeyU, KeyCode::KeyV, KeyCode::KeyW, KeyCode::KeyX,
+    KeyCode::KeyY, KeyCode::KeyZ,
+    KeyCode::Digit0, KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3, KeyCode::Digit4,
+    KeyCode::Digit5, KeyCode::Digit6, KeyCode::Digit7, KeyCode::Digit8, KeyCode::Digit9,
+    KeyCode::Numpad0, KeyCode::Numpad1, KeyCode::Numpad2, KeyCode::Numpad3, KeyCode::Numpad4,
+    KeyCode::Numpad5, KeyCode::Numpad6, KeyCode::Numpad7, KeyCode::Numpad8, KeyCode::Numpad9,
+    KeyCode::ArrowUp, KeyCode::ArrowDown, KeyCode::ArrowLeft, KeyCode::ArrowRight,
+    KeyCode::Space, KeyCode::Enter, KeyCode::Tab, KeyCode::Backspace,
+    KeyCode::Period, KeyCode::Comma, KeyCode::Slash, KeyCode::Semicolon, KeyCode::Quote,
+    KeyCode::Minus, KeyCode::Equal, KeyCode::BracketLeft, KeyCode::BracketRight,
+    KeyCode::Backslash, KeyCode::Backquote,
+    KeyCode::Home, KeyCode::Insert, KeyCode::Delete,
+    KeyCode::F1, KeyCode::F4, KeyCode::F5, KeyCode::F6,
+];
+
+/// Look up a key by its `KeyCode` name ("KeyW", "Numpad8", "Space"...)
+pub fn parse_key(name: &str) -> Option<KeyCode> {
+    NAMEABLE_KEYS.iter().copied().find(|key| format!("{key:?}") == name)
+}
+
+// ============================================================================
+// KEY BINDINGS RESOURCE
+// ============================================================================
+
+/// A key that was asked to trigger two different actions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyConflict {
+    pub key: KeyCode,
+    pub bound_to: PlayerAction,
+    pub rejected: PlayerAction,
+}
+
+impl fmt::Display for KeyConflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:?} is already bound to {}, not binding it to {}",
+            self.key,
+            self.bound_to.name(),
+            self.rejected.name()
+        )
+    }
+}
+
+/// Which key triggers which action (a key triggers at most one action)
+#[derive(Resource, Debug, Clone)]
+pub struct KeyBindings {
+    keys: HashMap<KeyCode, PlayerAction>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let (bindings, conflicts) = Self::from_layouts(&DEFAULT_LAYOUTS);
+        debug_assert!(conflicts.is_empty(), "default layouts conflict: {conflicts:?}");
+        bindings
+    }
+}
+
+impl KeyBindings {
+    pub fn empty() -> Self {
+        Self {
+            keys: HashMap::new(),
+        }
+    }
+
+    /// The action keys plus the given layouts, keeping the first binding for any shared key
+    pub fn from_layouts(layouts: &[KeyLayout]) -> (Self, Vec<KeyConflict>) {
+        let mut bindings = Self::empty();
+        let conflicts = layout_bindings(layouts)
+            .filter_map(|&(key, action)| bindings.bind(key, action).err())
+            .collect();
+        (bindings, conflicts)
+    }
+
+    /// Bind a key to an action, refusing keys already used by another action
+    pub fn bind(&mut self, key: KeyCode, action: PlayerAction) -> Result<(), KeyConflict> {
+        match self.keys.get(&key) {
+            Some(&bound_to) if bound_to != action => Err(KeyConflict { key, bound_to, rejected: action }),
+            _ => {
+                self.keys.insert(key, action);
+                Ok(())
+            }
+        }
+    }
+
+    /// Remove every key bound to an action
+    pub fn unbind(&mut self, action: PlayerAction) {
+        self.keys.retain(|_, bound| *bound != action);
+    }
+
+    pub fn action_for(&self, key: KeyCode) -> Option<PlayerAction> {
+        self.keys.get(&key).copied()
+    }
+
+    /// Keys bound to an action, in a stable order for display
+    pub fn keys_for(&self, action: PlayerAction) -> Vec<KeyCode> {
+        let mut keys: Vec<KeyCode> = self
+            .keys
+            .iter()
+            .filter(|(_, bound)| **bound == action)
+            .map(|(key, _)| *key)
+            .collect();
+        keys.sort_by_key(|key| format!("{key:?}"));
+        keys
+    }
+
+    /// Build bindings from the text of a bindings file
+    ///
+    /// ```text
+    /// # Movement layouts to use (default: wasd, arrows, numpad)
+    /// layouts = wasd, vi
+    /// # Replace the keys for an action
+    /// attack = Space, KeyF
+    /// ```
+    ///
+    /// Returns the bindings plus a description of every line that was
+    /// ignored, including keys that conflict with another action.
+    pub fn parse(text: &str) -> (Self, Vec<String>) {
+        let mut problems = Vec::new();
+        let mut layouts: Vec<KeyLayout> = DEFAULT_LAYOUTS.to_vec();
+        let mut overrides: Vec<(PlayerAction, Vec<KeyCode>)> = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let line_number = index + 1;
+
+            let Some((name, values)) = line.split_once('=') else {
+                problems.push(format!("line {line_number}: expected `name = value`"));
+                continue;
+            };
+            let name = name.trim();
+            let values = values.split(',').map(str::trim).filter(|value| !value.is_empty());
+
+            if name == "layouts" {
+                layouts.clear();
+                for value in values {
+                    match KeyLayout::from_name(value) {
+                        Some(layout) => layouts.push(layout),
+                        None => problems.push(format!("line {line_number}: unknown layout `{value}`")),
+                    }
+                }
+                continue;
+            }
+
+            let Some(action) = PlayerAction::from_name(name) else {
+                problems.push(format!("line {line_number}: unknown action `{name}`"));
+                continue;
+            };
+            let mut keys = Vec::new();
+            for value in values {
+                match parse_key(value) {
+                    Some(key) => keys.push(key),
+                    None => problems.push(format!("line {line_number}: unknown key `{value}`")),
+                }
+            }
+            overrides.push((action, keys));
+        }
+
+        // Overridden actions lose their layout keys, so the layouts can't
+        // claim a key the user has given to something else
+        let mut bindings = Self::empty();
+        for &(key, action) in layout_bindings(&layouts) {
+            let overridden = overrides.iter().any(|(overridden, _)| *overridden == action);
+            let claimed = overrides.iter().any(|(_, keys)| keys.contains(&key));
+            if overridden || claimed {
+                continue;
+            }
+            if let Err(conflict) = bindings.bind(key, action) {
+                problems.push(conflict.to_string());
+            }
+        }
+        for (action, keys) in &overrides {
+            for &key in keys {
+                if let Err(conflict) = bindings.bind(key, *action) {
+                    problems.push(conflict.to_string());
+                }
+            }
+        }
+
+        // Taking a layout key for another action can leave an action with none
+        for action in PlayerAction::ALL {
+            let had_keys = layout_bindings(&layouts).any(|(_, bound)| *bound == action);
+            if had_keys && bindings.keys_for(action).is_empty() {
+                problems.push(format!("{} has no keys left bound", action.name()));
+            }
+        }
+
+        (bindings, problems)
+    }
+}
+
+/// Action keys followed by every binding of the given layouts
+fn layout_bindings(layouts: &[KeyLayout]) -> impl Iterator<Item = &'static (KeyCode, PlayerAction)> + '_ {
+    ACTION_BINDINGS
+        .iter()
+        .chain(layouts.iter().flat_map(|layout| layout.bindings()))
+}
+
+// ============================================================================
+// ACTION INPUT RESOURCE
+// ============================================================================
+
+/// Actions triggered this frame, whatever produced them
+#[derive(Resource, Debug, Default)]
+pub struct ActionInput {
+    actions: Vec<PlayerAction>,
+}
+
+impl ActionInput {
+    pub fn press(&mut self, action: PlayerAction) {
+        if !self.actions.contains(&action) {
+            self.actions.push(action);
+        }
+    }
+
+    pub fn just_pressed(&self, action: PlayerAction) -> bool {
+        self.actions.contains(&action)
+    }
+
+    pub fn clear(&mut self) {
+        self.actions.clear();
+    }
+
+    /// Every action triggered this frame, in the order given
+    pub fn pressed(&self) -> &[PlayerAction] {
+        &self.actions
+    }
+
+    /// Combined step of every movement action pressed (clamped to one tile)
+    pub fn movement(&self) -> (i32, i32) {
+        let (dx, dy) = self
+            .actions
+            .iter()
+            .filter_map(|action| action.direction())
+            .fold((0, 0), |(x, y), (dx, dy)| (x + dx, y + dy));
+        (dx.clamp(-1, 1), dy.clamp(-1, 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bind_refuses_a_key_used_by_another_action() {
+        let mut bindings = KeyBindings::empty();
+        assert_eq!(bindings.bind(KeyCode::KeyF, PlayerAction::Attack), Ok(()));
+        assert_eq!(bindings.bind(KeyCode::KeyF, PlayerAction::Attack), Ok(()));
+        assert_eq!(
+            bindings.bind(KeyCode::KeyF, PlayerAction::Disarm),
+            Err(KeyConflict { key: KeyCode::KeyF, bound_to: PlayerAction::Attack, rejected: PlayerAction::Disarm }),
+        );
+        assert_eq!(bindings.action_for(KeyCode::KeyF), Some(PlayerAction::Attack));
+    }
+
+    #[test]
+    fn empty_file_gives_the_default_bindings() {
+        let (bindings, problems) = KeyBindings::parse("# nothing here\n\n");
+        assert!(problems.is_empty(), "{problems:?}");
+        for action in PlayerAction::ALL {
+            assert_eq!(bindings.keys_for(action), KeyBindings::default().keys_for(action));
+        }
+    }
+
+    #[test]
+    fn conflicting_overrides_keep_the_first_action() {
+        let (bindings, problems) = KeyBindings::parse("attack = KeyF\ndisarm = KeyF, KeyG");
+
+        assert_eq!(bindings.action_for(KeyCode::KeyF), Some(PlayerAction::Attack));
+        assert_eq!(bindings.keys_for(PlayerAction::Disarm), vec![KeyCode::KeyG]);
+        assert_eq!(problems, vec!["KeyF is already bound to attack, not binding it to disarm".to_string()]);
+    }
+
+    #[test]
+    fn override_can_take_a_layout_key() {
+        let (bindings, problems) = KeyBindings::parse("attack = KeyW");
+
+        assert!(problems.is_empty(), "{problems:?}");
+        assert_eq!(bindings.action_for(KeyCode::KeyW), Some(PlayerAction::Attack));
+        assert_eq!(bindings.action_for(KeyCode::Space), None);
+        // The other default layouts still move north
+        assert_eq!(bindings.keys_for(PlayerAction::MoveNorth), vec![KeyCode::ArrowUp, KeyCode::Numpad8]);
+    }
+
+    #[test]
+    fn warns_when_an_action_has_no_keys_left() {
+        let (bindings, problems) = KeyBindings::parse("layouts = wasd\nattack = KeyW");
+
+        assert!(bindings.keys_for(PlayerAction::MoveNorth).is_empty());
+        assert_eq!(problems, vec!["move_north has no keys left bound".to_string()]);
+    }
+
+    #[test]
+    fn reports_lines_it_cannot_use() {
+        let text = "layouts = wasd, dvorak\njump = Space\nattack = Space, KeyNope\nnonsense";
+        let (bindings, problems) = KeyBindings::parse(text);
+
+        assert_eq!(
+            problems,
+            vec![
+                "line 1: unknown layout `dvorak`".to_string(),
+                "line 2: unknown action `jump`".to_string(),
+                "line 3: unknown key `KeyNope`".to_string(),
+                "line 4: expected `name = value`".to_string(),
+            ],
+        );
+        // The usable parts still apply
+        assert_eq!(bindings.action_for(KeyCode::KeyW), Some(PlayerAction::MoveNorth));
+        assert_eq!(bindings.action_for(KeyCode::ArrowUp), None);
+        assert_eq!(bindings.keys_for(PlayerAction::Attack), vec![KeyCode::Space]);
+    }
+}
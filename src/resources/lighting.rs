@@ -0,0 +1,83 @@
+/// Light map - how brightly, and in what color, each tile is lit
+
+use bevy::prelude::*;
+use crate::resources::map::Position;
+use crate::constants::{MIN_VISIBLE_LIGHT, AMBIENT_LIGHT_BY_DEPTH};
+
+/// Per-tile light levels and tints, rebuilt when lights or the map change
+#[derive(Resource, Debug, Default)]
+pub struct LightMap {
+    width: usize,
+    height: usize,
+    /// Light level per tile (ambient included, capped at 1.0 when read)
+    levels: Vec<f32>,
+    /// Light-weighted sum of the colors reaching each tile
+    tints: Vec<Vec3>,
+    pub ambient: f32,
+}
+
+impl LightMap {
+    /// An unlit map of the given size with uniform ambient light
+    pub fn new(width: usize, height: usize, ambient: f32) -> Self {
+        Self {
+            width,
+            height,
+            levels: vec![ambient; width * height],
+            tints: vec![Vec3::splat(ambient); width * height],
+            ambient,
+        }
+    }
+
+    /// Ambient light for a dungeon depth (darker the deeper you go)
+    pub fn ambient_for_depth(depth: u32) -> f32 {
+        let index = (depth.max(1) as usize - 1).min(AMBIENT_LIGHT_BY_DEPTH.len() - 1);
+        AMBIENT_LIGHT_BY_DEPTH[index]
+    }
+
+    fn index(&self, pos: Position) -> Option<usize> {
+        let in_bounds = pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width && (pos.y as usize) < self.height;
+        in_bounds.then(|| pos.y as usize * self.width + pos.x as usize)
+    }
+
+    /// Add `amount` of colored light to a tile
+    pub fn add_light(&mut self, pos: Position, amount: f32, color: Color) {
+        if let Some(idx) = self.index(pos) {
+            let c = color.to_srgba();
+            self.levels[idx] += amount;
+            self.tints[idx] += Vec3::new(c.red, c.green, c.blue) * amount;
+        }
+    }
+
+    /// Light level of a tile, 0.0 (dark) to 1.0 (fully lit)
+    pub fn level(&self, pos: Position) -> f32 {
+        self.index(pos).map_or(0.0, |idx| self.levels[idx].min(1.0))
+    }
+
+    /// Whether there is enough light on a tile to see it
+    pub fn is_lit(&self, pos: Position) -> bool {
+        self.level(pos) >= MIN_VISIBLE_LIGHT
+    }
+
+    /// Average color of the light on a tile (white when unlit)
+    pub fn tint(&self, pos: Position) -> Color {
+        match self.index(pos) {
+            Some(idx) if self.levels[idx] > 0.0 => {
+                let tint = self.tints[idx] / self.levels[idx];
+                Color::srgb(tint.x, tint.y, tint.z)
+            }
+            _ => Color::WHITE,
+        }
+    }
+
+    /// A tile's color as it appears under this tile's light
+    pub fn shade(&self, color: Color, pos: Position) -> Color {
+        let c = color.to_srgba();
+        let tint = self.tint(pos).to_srgba();
+        let brightness = 0.4 + 0.6 * self.level(pos);
+        Color::srgb(
+            c.red * tint.red * brightness,
+            c.green * tint.green * brightness,
+            c.blue * tint.blue * brightness,
+        )
+    }
+}
@@ -3,18 +3,50 @@
 use bevy::prelude::*;
 use std::collections::HashMap;
 use bracket_pathfinding::prelude::*;
+use rand::Rng;
+use crate::resources::dijkstra::NEIGHBOURS;
+use crate::resources::rng::GameRng;
+use crate::constants::{FINAL_DEPTH, TILE_FLOOR, TILE_DOWN_STAIRS, TILE_WALL_BASE};
 
 /// Types of tiles in the game world
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TileType {
     Floor,
     Wall,
+    /// Leads to the next depth
+    DownStairs,
 }
 
 impl TileType {
+    /// Short description for tooltips
+    pub fn name(&self) -> &'static str {
+        match self {
+            TileType::Floor => "Floor",
+            TileType::Wall => "Wall",
+            TileType::DownStairs => "Stairs down",
+        }
+    }
+
     /// Check if this tile can be walked on
     pub fn is_walkable(&self) -> bool {
-        matches!(self, TileType::Floor)
+        matches!(self, TileType::Floor | TileType::DownStairs)
+    }
+
+    /// Character drawn for this tile in glyph mode
+    pub fn glyph(&self) -> char {
+        match self {
+            TileType::Floor => '.',
+            TileType::Wall => '#',
+            TileType::DownStairs => '>',
+        }
+    }
+
+    /// Cost of stepping onto this tile (None if it can't be entered)
+    pub fn movement_cost(&self) -> Option<i32> {
+        match self {
+            TileType::Floor | TileType::DownStairs => Some(1),
+            TileType::Wall => None,
+        }
     }
 }
 
@@ -31,6 +63,36 @@ impl Position {
     }
 }
 
+/// Chebyshev (king-move) distance between two positions
+pub fn grid_distance(a: Position, b: Position) -> i32 {
+    (a.x - b.x).abs().max((a.y - b.y).abs())
+}
+
+/// Tiles on a Bresenham line from `from` to `to` (excluding `from`, including `to`)
+pub fn line_between(from: Position, to: Position) -> Vec<Position> {
+    let mut tiles = Vec::new();
+    let dx = (to.x - from.x).abs();
+    let dy = -(to.y - from.y).abs();
+    let step_x = if from.x < to.x { 1 } else { -1 };
+    let step_y = if from.y < to.y { 1 } else { -1 };
+    let mut error = dx + dy;
+    let (mut x, mut y) = (from.x, from.y);
+
+    while (x, y) != (to.x, to.y) {
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            x += step_x;
+        }
+        if doubled <= dx {
+            error += dx;
+            y += step_y;
+        }
+        tiles.push(Position::new(x, y));
+    }
+    tiles
+}
+
 /// The current game map
 #[derive(Resource)]
 pub struct CurrentMap {
@@ -38,6 +100,8 @@ pub struct CurrentMap {
     pub width: usize,
     pub height: usize,
     pub entities_at: HashMap<Position, Vec<Entity>>,
+    /// Interior of the boss arena vault, on the final depth
+    pub arena: Option<IRect>,
 }
 
 impl CurrentMap {
@@ -49,6 +113,7 @@ impl CurrentMap {
             width,
             height,
             entities_at: HashMap::new(),
+            arena: None,
         }
     }
 
@@ -75,6 +140,70 @@ impl CurrentMap {
         map
     }
 
+    /// Build the map for a dungeon depth
+    ///
+    /// Every depth has down stairs except the final one, which instead
+    /// always holds the boss arena.
+    pub fn for_depth(depth: u32, rng: &mut GameRng) -> Self {
+        let mut map = Self::test_map();
+        if depth >= FINAL_DEPTH {
+            map.carve_arena_vault();
+        } else {
+            map.place_down_stairs(rng);
+        }
+        map
+    }
+
+    /// Wall off a pillared arena with a single door in the top half of the map
+    pub fn carve_arena_vault(&mut self) {
+        let outer = IRect::new(3, 12, 16, 18);
+
+        for y in outer.min.y..=outer.max.y {
+            for x in outer.min.x..=outer.max.x {
+                let on_edge = x == outer.min.x || x == outer.max.x || y == outer.min.y || y == outer.max.y;
+                self.tiles[y as usize][x as usize] = if on_edge { TileType::Wall } else { TileType::Floor };
+            }
+        }
+
+        // Door facing the rest of the level
+        let door = Position::new(outer.center().x, outer.min.y);
+        self.tiles[door.y as usize][door.x as usize] = TileType::Floor;
+
+        // Pillars to fight around
+        for (x, y) in [(6, 14), (13, 14), (6, 16), (13, 16)] {
+            self.tiles[y][x] = TileType::Wall;
+        }
+
+        self.arena = Some(IRect::new(outer.min.x + 1, outer.min.y + 1, outer.max.x - 1, outer.max.y - 1));
+    }
+
+    /// Whether a position lies inside the boss arena
+    pub fn in_arena(&self, pos: Position) -> bool {
+        self.arena.is_some_and(|arena| arena.contains(IVec2::new(pos.x, pos.y)))
+    }
+
+    /// Turn a random floor tile into the down stairs
+    pub fn place_down_stairs(&mut self, rng: &mut GameRng) {
+        for _ in 0..100 {
+            let Some(pos) = self.random_walkable_position(rng) else {
+                break;
+            };
+            if self.tiles[pos.y as usize][pos.x as usize] == TileType::Floor {
+                self.tiles[pos.y as usize][pos.x as usize] = TileType::DownStairs;
+                return;
+            }
+        }
+        warn!("Could not find a floor tile for the down stairs");
+    }
+
+    /// Position of the down stairs, if this level has any
+    pub fn down_stairs(&self) -> Option<Position> {
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .find(|(x, y)| self.tiles[*y][*x] == TileType::DownStairs)
+            .map(|(x, y)| Position::new(x as i32, y as i32))
+    }
+
     /// Get the tile type at a position
     pub fn get_tile(&self, x: i32, y: i32) -> Option<TileType> {
         if x < 0 || y < 0 {
@@ -94,6 +223,53 @@ impl CurrentMap {
             .unwrap_or(false)
     }
 
+    /// Bitmask of the walls orthogonally next to a tile (N=1, E=2, S=4, W=8)
+    ///
+    /// Off-map counts as wall so the border joins up.
+    pub fn wall_mask(&self, x: i32, y: i32) -> usize {
+        [(0, 1), (1, 0), (0, -1), (-1, 0)]
+            .iter()
+            .enumerate()
+            .filter(|(_, (dx, dy))| matches!(self.get_tile(x + dx, y + dy), Some(TileType::Wall) | None))
+            .map(|(bit, _)| 1 << bit)
+            .sum()
+    }
+
+    /// Tileset index for a tile, with walls autotiled to their neighbours
+    pub fn tile_index(&self, x: i32, y: i32) -> Option<usize> {
+        match self.get_tile(x, y)? {
+            TileType::Floor => Some(TILE_FLOOR),
+            TileType::DownStairs => Some(TILE_DOWN_STAIRS),
+            TileType::Wall => Some(TILE_WALL_BASE + self.wall_mask(x, y)),
+        }
+    }
+
+    /// Cost of stepping onto a position (None if it can't be entered)
+    pub fn movement_cost(&self, x: i32, y: i32) -> Option<i32> {
+        self.get_tile(x, y).and_then(|t| t.movement_cost())
+    }
+
+    /// Check that no wall lies strictly between two positions
+    pub fn has_clear_line(&self, from: Position, to: Position) -> bool {
+        line_between(from, to)
+            .iter()
+            .filter(|pos| **pos != to)
+            .all(|pos| self.is_walkable(pos.x, pos.y))
+    }
+
+    /// Pick a random walkable position (None if none found after 100 attempts)
+    pub fn random_walkable_position(&self, rng: &mut GameRng) -> Option<Position> {
+        for _ in 0..100 {
+            let x = rng.gen_range(0..self.width) as i32;
+            let y = rng.gen_range(0..self.height) as i32;
+
+            if self.is_walkable(x, y) {
+                return Some(Position::new(x, y));
+            }
+        }
+        None
+    }
+
     /// Check if there's a blocking entity at this position
     pub fn is_blocked(&self, pos: &Position) -> bool {
         !self.is_walkable(pos.x, pos.y)
@@ -114,9 +290,25 @@ impl BaseMap for CurrentMap {
         !self.is_walkable(x, y)
     }
 
-    fn get_available_exits(&self, _idx: usize) -> SmallVec<[(usize, f32); 10]> {
-        // Not needed for FOV, only for pathfinding
-        SmallVec::new()
+    fn get_available_exits(&self, idx: usize) -> SmallVec<[(usize, f32); 10]> {
+        let x = (idx % self.width) as i32;
+        let y = (idx / self.width) as i32;
+
+        // Eight-way movement weighted by terrain cost
+        let mut exits = SmallVec::new();
+        for (dx, dy) in NEIGHBOURS {
+            if let Some(cost) = self.movement_cost(x + dx, y + dy) {
+                let exit_idx = self.point2d_to_index(Point::new(x + dx, y + dy));
+                exits.push((exit_idx, cost as f32));
+            }
+        }
+        exits
+    }
+
+    fn get_pathing_distance(&self, idx1: usize, idx2: usize) -> f32 {
+        let p1 = self.index_to_point2d(idx1);
+        let p2 = self.index_to_point2d(idx2);
+        DistanceAlg::Chebyshev.distance2d(p1, p2)
     }
 }
 
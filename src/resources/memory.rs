@@ -0,0 +1,48 @@
+/// Entity memory - what the player last saw on each tile
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::resources::map::Position;
+
+/// A non-actor entity as the player last saw it
+#[derive(Debug, Clone)]
+pub struct RememberedThing {
+    pub entity: Entity,
+    pub name: String,
+}
+
+/// Per-tile memory of the items, traps and features last seen there
+///
+/// Only refreshed while a tile is in view, so it can go stale: something
+/// picked up or destroyed out of sight is still remembered until the player
+/// looks again.
+#[derive(Resource, Default)]
+pub struct EntityMemory {
+    tiles: HashMap<Position, Vec<RememberedThing>>,
+}
+
+impl EntityMemory {
+    /// Replace what is remembered on a tile with what is there now
+    pub fn remember(&mut self, pos: Position, things: Vec<RememberedThing>) {
+        if things.is_empty() {
+            self.tiles.remove(&pos);
+        } else {
+            self.tiles.insert(pos, things);
+        }
+    }
+
+    /// Everything remembered on a tile
+    pub fn at(&self, pos: Position) -> &[RememberedThing] {
+        self.tiles.get(&pos).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Whether the entity was last seen on this tile
+    pub fn remembers(&self, entity: Entity, pos: Position) -> bool {
+        self.at(pos).iter().any(|thing| thing.entity == entity)
+    }
+
+    /// Forget everything (new level)
+    pub fn clear(&mut self) {
+        self.tiles.clear();
+    }
+}
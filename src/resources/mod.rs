@@ -6,8 +6,31 @@ pub mod map;
 pub mod visibility;
 pub mod action_points;
 pub mod combat_log;
+pub mod dijkstra;
+pub mod bestiary;
+pub mod factions;
+pub mod depth;
+pub mod keybindings;
+pub mod memory;
+pub mod lighting;
+pub mod tileset;
+pub mod action_source;
+pub mod rng;
 
-pub use map::{TileType, CurrentMap};
+pub use map::{TileType, CurrentMap, grid_distance, line_between};
 pub use visibility::{VisibilityState, VisibilityMap};
 pub use action_points::PlayerActionPoints;
-pub use combat_log::CombatLog;
+pub use combat_log::{CombatLog, LogEntry, LogEntryKind};
+pub use dijkstra::{DijkstraMap, DijkstraMaps};
+pub use bestiary::{Bestiary, MonsterTemplate};
+pub use factions::{FactionTable, Allegiance};
+pub use depth::Depth;
+pub use memory::{EntityMemory, RememberedThing};
+pub use lighting::LightMap;
+pub use tileset::{Tileset, TilesetAtlas, RenderMode, cp437_index};
+pub use keybindings::{
+    PlayerAction, KeyLayout, KeyBindings, KeyConflict, ActionInput, ACTION_BINDINGS, DEFAULT_LAYOUTS,
+    parse_key,
+};
+pub use action_source::{ActionSource, ActionReplay, ActionRecording};
+pub use rng::GameRng;
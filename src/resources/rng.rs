@@ -0,0 +1,56 @@
+/// Seeded random numbers - the one source of randomness in the game rules
+///
+/// Level generation, spawning, combat rolls, traps and AI all draw from
+/// `GameRng`, so a game started from the same seed with the same player
+/// actions plays out the same way. That makes bot crashes and replays
+/// reproducible.
+
+use bevy::prelude::*;
+use rand::{RngCore, SeedableRng};
+use rand::rngs::StdRng;
+
+/// The game's random number generator and the seed it started from
+#[derive(Resource, Debug, Clone)]
+pub struct GameRng {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl Default for GameRng {
+    /// A fresh random seed
+    fn default() -> Self {
+        Self::new(rand::random())
+    }
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Seed this game started from (to play it again)
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
@@ -0,0 +1,68 @@
+/// Tileset and glyph font - the optional sprite sheets used instead of colored squares
+
+use bevy::prelude::*;
+
+/// Image and grid layout of a loaded sprite sheet
+#[derive(Debug, Clone)]
+pub struct TilesetAtlas {
+    pub image: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
+}
+
+/// The loaded tileset and glyph font, if there are any
+///
+/// Without a tileset every `Renderable` is drawn as a colored square.
+#[derive(Resource, Debug, Default)]
+pub struct Tileset {
+    pub atlas: Option<TilesetAtlas>,
+    /// CP437 bitmap font used in glyph mode
+    pub glyph_font: Option<TilesetAtlas>,
+}
+
+/// How the map and actors are drawn
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RenderMode {
+    /// Tileset sprites (colored squares without a tileset)
+    #[default]
+    Tiles,
+    /// Classic CP437 characters
+    Glyphs,
+}
+
+/// Position of a character in the CP437 font (unknown characters show as '?')
+pub fn cp437_index(glyph: char) -> usize {
+    if glyph.is_ascii() {
+        return glyph as usize;
+    }
+    match glyph {
+        '☺' => 1,
+        '☻' => 2,
+        '♥' => 3,
+        '♦' => 4,
+        '♣' => 5,
+        '♠' => 6,
+        '•' => 7,
+        '○' => 9,
+        '♂' => 11,
+        '♀' => 12,
+        '♪' => 13,
+        '☼' => 15,
+        '►' => 16,
+        '◄' => 17,
+        '↑' => 24,
+        '↓' => 25,
+        '→' => 26,
+        '←' => 27,
+        '▲' => 30,
+        '▼' => 31,
+        '░' => 176,
+        '▒' => 177,
+        '▓' => 178,
+        '█' => 219,
+        'Ω' => 234,
+        '∞' => 236,
+        '≡' => 240,
+        '·' => 250,
+        _ => '?' as usize,
+    }
+}
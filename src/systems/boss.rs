@@ -0,0 +1,75 @@
+/// Boss systems - the final-depth encounter, its phases and victory
+
+use bevy::prelude::*;
+use crate::components::{Position, Name, Health, CombatStats, Spellbook, Boss, AiState};
+use crate::resources::{CurrentMap, CombatLog, LogEntryKind, Bestiary};
+use crate::systems::combat::EntityKilled;
+use crate::systems::enemy_spawning::spawn_monster;
+use crate::states::GameState;
+
+// ============================================================================
+// SPAWNING
+// ============================================================================
+
+/// Put the boss in the middle of the map's arena vault, if it has one
+pub fn spawn_boss(commands: &mut Commands, map: &CurrentMap, bestiary: &Bestiary) {
+    let Some(arena) = map.arena else {
+        return;
+    };
+    let Some(template) = bestiary.get("lich") else {
+        warn!("Bestiary has no boss template");
+        return;
+    };
+
+    let center = arena.center();
+    let pos = Position::new(center.x, center.y);
+    spawn_monster(commands, template, pos, template.name.to_string(), AiState::Wandering);
+    info!("{} awaits in the arena at ({}, {})", template.name, pos.x, pos.y);
+}
+
+// ============================================================================
+// PHASES
+// ============================================================================
+
+/// Move bosses into their next phase as their health drops
+///
+/// Each phase swaps in a new spellbook (ready to cast at once) and can make
+/// the boss hit harder.
+pub fn boss_phase_system(
+    mut commands: Commands,
+    mut combat_log: ResMut<CombatLog>,
+    mut boss_query: Query<(Entity, &Health, &mut Boss, &mut CombatStats), Changed<Health>>,
+) {
+    for (entity, health, mut boss, mut stats) in boss_query.iter_mut() {
+        if health.is_dead() {
+            continue;
+        }
+
+        while let Some(phase) = boss.next_phase(health.percentage()) {
+            boss.phases_entered += 1;
+            stats.power += phase.power_bonus;
+            commands.entity(entity).insert(Spellbook::new(phase.spells));
+            combat_log.add_entry(LogEntryKind::Kill, phase.announcement.to_string());
+        }
+    }
+}
+
+// ============================================================================
+// VICTORY
+// ============================================================================
+
+/// Win the game when a boss dies
+pub fn check_boss_victory_system(
+    mut killed_events: EventReader<EntityKilled>,
+    boss_query: Query<&Name, With<Boss>>,
+    mut combat_log: ResMut<CombatLog>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for killed in killed_events.read() {
+        if let Ok(name) = boss_query.get(killed.entity) {
+            combat_log.add_entry(LogEntryKind::Kill, format!("{} is destroyed. You are victorious!", name.0));
+            info!("Boss defeated! Victory!");
+            next_state.set(GameState::Victory);
+        }
+    }
+}
@@ -0,0 +1,82 @@
+/// Built-in bot - plays the player's turns for automated playthroughs
+///
+/// Fights the nearest hostile it can see, rests when hurt, explores the
+/// level and takes the stairs once there is nothing left to explore. It only
+/// goes by what the player knows, and acts through the same `PlayerAction`s
+/// as the keyboard.
+
+use bevy::prelude::*;
+use std::collections::HashSet;
+use crate::components::{Player, Position};
+use crate::resources::{
+    CurrentMap, VisibilityMap, VisibilityState, DijkstraMap, FactionTable, Allegiance, ActionInput, PlayerAction,
+    grid_distance,
+};
+use crate::systems::explore::{AutoExplore, PlayerSight, OtherActor, KnownTrapFilter};
+use crate::systems::rest::Resting;
+use crate::constants::BOT_REST_BELOW_PERCENT;
+
+/// Movement action one step along the known map towards `goal`, around known traps
+fn step_towards(
+    map: &CurrentMap,
+    visibility_map: &VisibilityMap,
+    traps: &HashSet<Position>,
+    from: Position,
+    goal: Position,
+) -> Option<PlayerAction> {
+    let known = |pos: Position| visibility_map.get(&pos) != VisibilityState::Unseen && !traps.contains(&pos);
+    let step = DijkstraMap::build_with(map, &[goal], known).downhill_step(map, from, |_| false)?;
+    PlayerAction::for_direction(step.x - from.x, step.y - from.y)
+}
+
+/// Choose the player's action for this frame
+#[allow(clippy::too_many_arguments)]
+pub fn bot_actions_system(
+    mut actions: ResMut<ActionInput>,
+    auto_explore: Res<AutoExplore>,
+    resting: Res<Resting>,
+    map: Res<CurrentMap>,
+    visibility_map: Res<VisibilityMap>,
+    factions: Res<FactionTable>,
+    player_query: Query<PlayerSight, With<Player>>,
+    actor_query: Query<OtherActor, Without<Player>>,
+    trap_query: Query<&Position, KnownTrapFilter>,
+) {
+    let Ok((player, pos, viewshed, health, faction, grudges)) = player_query.get_single() else {
+        return;
+    };
+    let traps: HashSet<Position> = trap_query.iter().copied().collect();
+    let me = Allegiance::new(player, faction, grudges);
+
+    let nearest_hostile = actor_query
+        .iter()
+        .filter(|(_, other_pos, ..)| viewshed.can_see(other_pos))
+        .filter(|(other, _, _, faction, grudges)| factions.is_hostile(me, Allegiance::new(*other, faction, *grudges)))
+        .map(|(_, other_pos, ..)| *other_pos)
+        .min_by_key(|other_pos| grid_distance(*pos, *other_pos));
+
+    let hurt = health.current * 100 < health.max * BOT_REST_BELOW_PERCENT;
+    let frontier_left = || {
+        DijkstraMap::explore_map(&map, &visibility_map, |pos| traps.contains(&pos))
+            .downhill_step(&map, *pos, |_| false)
+            .is_some()
+    };
+
+    let action = match nearest_hostile {
+        Some(target) if grid_distance(*pos, target) <= 1 => Some(PlayerAction::Attack),
+        Some(target) => step_towards(&map, &visibility_map, &traps, *pos, target).or(Some(PlayerAction::Wait)),
+        // Let an ongoing rest or exploration carry on
+        None if resting.active || auto_explore.active => None,
+        None if hurt => Some(PlayerAction::Rest),
+        None if frontier_left() => Some(PlayerAction::AutoExplore),
+        None => match map.down_stairs().filter(|stairs| visibility_map.get(stairs) != VisibilityState::Unseen) {
+            Some(stairs) if stairs == *pos => Some(PlayerAction::Descend),
+            Some(stairs) => step_towards(&map, &visibility_map, &traps, *pos, stairs).or(Some(PlayerAction::Wait)),
+            None => Some(PlayerAction::Wait),
+        },
+    };
+
+    if let Some(action) = action {
+        actions.press(action);
+    }
+}
@@ -1,10 +1,21 @@
-/// Combat systems for player attacks, damage calculation, and death handling
+/// Combat systems for attacks, damage calculation, and death handling
 
 use bevy::prelude::*;
-use crate::components::{Player, Position, Viewshed, Health, CombatStats, Enemy, Name};
-use crate::resources::{PlayerActionPoints, CombatLog};
+use crate::components::{
+    Player, Position, Viewshed, Health, CombatStats, DamageType, Resistance, Resistances, Weapon,
+    Name, AiState, Sneaking, Faction, Grudges,
+};
+use crate::resources::{
+    PlayerActionPoints, CombatLog, LogEntryKind, FactionTable, Allegiance, ActionInput, PlayerAction, GameRng,
+};
+use rand::Rng;
 use crate::states::GameState;
-use crate::constants::{ATTACK_ACTION_COST, BASE_HIT_CHANCE, DAMAGE_VARIANCE};
+use crate::constants::{
+    ATTACK_ACTION_COST, BASE_HIT_CHANCE, DAMAGE_VARIANCE,
+    HIT_CHANCE_PER_POINT, MIN_HIT_CHANCE, MAX_HIT_CHANCE,
+    CRITICAL_HIT_CHANCE, CRITICAL_HIT_MULTIPLIER,
+    GLANCING_BLOW_CHANCE, GLANCING_BLOW_MULTIPLIER, SNEAK_ATTACK_MULTIPLIER,
+};
 
 // ============================================================================
 // RESOURCES
@@ -16,23 +27,148 @@ pub struct PendingAttack {
     pub target: Option<Entity>,
 }
 
+/// Running count of combat events, shared by every kind
+///
+/// Each kind of combat event has its own queue, so the order they were sent
+/// in is lost when they are read; `seq` on each event restores it for the log.
+#[derive(Resource, Default)]
+pub struct CombatSequence(u64);
+
+impl CombatSequence {
+    /// Number for the next combat event
+    pub fn advance(&mut self) -> u64 {
+        self.0 += 1;
+        self.0
+    }
+}
+
+/// Query data for anything that can take part in combat
+type Combatant = (
+    &'static CombatStats,
+    &'static mut Health,
+    Option<&'static Weapon>,
+    Option<&'static Resistances>,
+    Option<&'static AiState>,
+    Option<&'static Sneaking>,
+);
+
+// ============================================================================
+// EVENTS
+// ============================================================================
+
+/// How an attack reaches its target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttackKind {
+    /// A blow with the attacker's weapon
+    Melee,
+    /// A shot with the attacker's weapon (bows)
+    Ranged,
+    /// A spell; the weapon plays no part
+    Spell,
+}
+
+/// Request for one entity to attack another
+///
+/// Sent by the player's attack input and by enemy AI; consumed by
+/// `resolve_attack_system`.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct AttackIntent {
+    pub attacker: Entity,
+    pub defender: Entity,
+    pub kind: AttackKind,
+    /// Damage type override (spells); None uses the attacker's Weapon
+    pub damage_type: Option<DamageType>,
+}
+
+impl AttackIntent {
+    /// Attack with whatever the attacker is wielding
+    pub fn melee(attacker: Entity, defender: Entity) -> Self {
+        Self { attacker, defender, kind: AttackKind::Melee, damage_type: None }
+    }
+
+    /// Shoot with whatever the attacker is wielding
+    pub fn ranged(attacker: Entity, defender: Entity) -> Self {
+        Self { attacker, defender, kind: AttackKind::Ranged, damage_type: None }
+    }
+
+    /// Spell attack dealing a specific damage type
+    pub fn typed(attacker: Entity, defender: Entity, damage_type: DamageType) -> Self {
+        Self { attacker, defender, kind: AttackKind::Spell, damage_type: Some(damage_type) }
+    }
+}
+
+/// An attack failed to connect
+#[derive(Event, Debug, Clone, Copy)]
+pub struct AttackMissed {
+    pub attacker: Entity,
+    pub defender: Entity,
+    pub kind: AttackKind,
+    /// Position among all combat events (see `CombatSequence`)
+    pub seq: u64,
+}
+
+/// Damage was applied to an entity
+///
+/// `source` is None for environmental damage such as traps.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DamageDealt {
+    pub source: Option<Entity>,
+    pub target: Entity,
+    pub amount: i32,
+    pub damage_type: DamageType,
+    /// How the blow landed (always `Hit` for environmental damage)
+    pub outcome: AttackOutcome,
+    /// Resistance that modified the damage, if any
+    pub resistance: Option<Resistance>,
+    /// Target's health after the damage was applied
+    pub remaining: i32,
+    /// How the blow was delivered (None for environmental damage)
+    pub kind: Option<AttackKind>,
+    /// Position among all combat events (see `CombatSequence`)
+    pub seq: u64,
+}
+
+/// An entity's health reached zero
+#[derive(Event, Debug, Clone, Copy)]
+pub struct EntityKilled {
+    pub entity: Entity,
+    pub killer: Option<Entity>,
+    /// Position among all combat events (see `CombatSequence`)
+    pub seq: u64,
+}
+
+/// Health was restored to an entity
+#[derive(Event, Debug, Clone, Copy)]
+pub struct HealApplied {
+    pub source: Option<Entity>,
+    pub target: Entity,
+    pub amount: i32,
+    /// Position among all combat events (see `CombatSequence`)
+    pub seq: u64,
+}
+
 // ============================================================================
 // INPUT SYSTEM
 // ============================================================================
 
-/// Capture Space bar input and queue attack on adjacent visible enemy
+/// Capture the attack action and queue an attack on an adjacent visible actor
+///
+/// Hostile actors are preferred; failing that, a neutral one is attacked
+/// (which turns it hostile). Friendly actors are never attacked.
+#[allow(clippy::type_complexity)]
 pub fn player_attack_input_system(
-    keyboard: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionInput>,
     mut pending_attack: ResMut<PendingAttack>,
     action_points: Res<PlayerActionPoints>,
-    player_query: Query<(&Position, &Viewshed), With<Player>>,
-    enemy_query: Query<(Entity, &Position, &Name), With<Enemy>>,
+    factions: Res<FactionTable>,
+    player_query: Query<(Entity, &Position, &Viewshed, &Faction, Option<&Grudges>), With<Player>>,
+    actor_query: Query<(Entity, &Position, &Name, &Faction, Option<&Grudges>), Without<Player>>,
 ) {
     // Clear previous pending attack
     pending_attack.target = None;
 
-    // Only process if Space bar pressed
-    if !keyboard.just_pressed(KeyCode::Space) {
+    // Only process if attack was pressed
+    if !actions.just_pressed(PlayerAction::Attack) {
         return;
     }
 
@@ -44,13 +180,16 @@ pub fn player_attack_input_system(
     }
 
     // Get player position and viewshed
-    let (player_pos, player_viewshed) = match player_query.get_single() {
+    let (player, player_pos, player_viewshed, player_faction, player_grudges) = match player_query.get_single() {
         Ok(data) => data,
         Err(_) => return,
     };
+    let me = Allegiance::new(player, player_faction, player_grudges);
 
-    // Find adjacent enemy in FOV
-    for (enemy_entity, enemy_pos, enemy_name) in enemy_query.iter() {
+    let mut neutral_target = None;
+
+    // Find adjacent hostile in FOV
+    for (enemy_entity, enemy_pos, enemy_name, faction, grudges) in actor_query.iter() {
         // Check adjacency (including diagonals)
         let dx = (player_pos.x - enemy_pos.x).abs();
         let dy = (player_pos.y - enemy_pos.y).abs();
@@ -65,12 +204,27 @@ pub fn player_attack_input_system(
             continue;
         }
 
+        let other = Allegiance::new(enemy_entity, faction, grudges);
+        if factions.is_friendly(me, other) {
+            continue;
+        }
+        if !factions.is_hostile(me, other) {
+            neutral_target.get_or_insert((enemy_entity, enemy_name));
+            continue;
+        }
+
         // Found valid target!
         pending_attack.target = Some(enemy_entity);
         info!("Attack queued: {} at ({}, {})", enemy_name.0, enemy_pos.x, enemy_pos.y);
         return;
     }
 
+    if let Some((neutral, name)) = neutral_target {
+        pending_attack.target = Some(neutral);
+        info!("Attack queued on neutral {}", name.0);
+        return;
+    }
+
     info!("No adjacent enemies to attack!");
 }
 
@@ -78,14 +232,12 @@ pub fn player_attack_input_system(
 // COMBAT EXECUTION SYSTEM
 // ============================================================================
 
-/// Execute pending attack: roll hit, calculate damage, apply to health
+/// Turn the player's pending attack into an AttackIntent and pay for it
 pub fn execute_attack_system(
-    mut commands: Commands,
     mut pending_attack: ResMut<PendingAttack>,
     mut action_points: ResMut<PlayerActionPoints>,
-    mut combat_log: ResMut<CombatLog>,
-    player_query: Query<(&CombatStats, &Name), With<Player>>,
-    mut enemy_query: Query<(&mut Health, &CombatStats, &Name), With<Enemy>>,
+    mut attack_intents: EventWriter<AttackIntent>,
+    player_query: Query<Entity, With<Player>>,
 ) {
     // Check if there's a pending attack
     let target = match pending_attack.target.take() {
@@ -93,89 +245,375 @@ pub fn execute_attack_system(
         None => return,
     };
 
-    // Get attacker stats (player)
-    let (attacker_stats, attacker_name) = match player_query.get_single() {
-        Ok(data) => data,
+    let player = match player_query.get_single() {
+        Ok(entity) => entity,
         Err(_) => return,
     };
 
-    // Get defender stats (enemy)
-    let (mut defender_health, defender_stats, defender_name) = match enemy_query.get_mut(target) {
-        Ok(data) => data,
-        Err(_) => {
-            info!("Target enemy no longer exists!");
-            return;
+    attack_intents.send(AttackIntent::melee(player, target));
+
+    // Spend action point
+    action_points.spend(ATTACK_ACTION_COST);
+}
+
+/// Resolve every queued attack between any two combatants
+///
+/// Works for player-vs-monster and monster-vs-player alike: the only
+/// requirement is that both entities have `CombatStats` and `Health`.
+/// Outcomes are reported as combat events rather than log text.
+pub fn resolve_attack_system(
+    mut attack_intents: EventReader<AttackIntent>,
+    mut missed_events: EventWriter<AttackMissed>,
+    mut damage_events: EventWriter<DamageDealt>,
+    mut killed_events: EventWriter<EntityKilled>,
+    mut sequence: ResMut<CombatSequence>,
+    mut rng: ResMut<GameRng>,
+    mut combatants: Query<Combatant>,
+) {
+    for intent in attack_intents.read() {
+        // Get attacker stats; dead attackers don't get to swing
+        let (attacker_stats, weapon_damage_type, attacker_sneaking) = match combatants.get(intent.attacker) {
+            Ok((stats, health, weapon, _, _, sneaking)) if !health.is_dead() => {
+                (*stats, weapon.map(|w| w.damage_type).unwrap_or_default(), sneaking.is_some())
+            }
+            _ => continue,
+        };
+        let damage_type = intent.damage_type.unwrap_or(weapon_damage_type);
+
+        // Get defender stats
+        let (defender_stats, mut defender_health, _, defender_resistances, defender_ai, _) = match combatants.get_mut(intent.defender) {
+            Ok(data) => data,
+            Err(_) => {
+                info!("Attack target no longer exists!");
+                continue;
+            }
+        };
+
+        // Already killed earlier this turn (despawn is deferred)
+        if defender_health.is_dead() {
+            continue;
         }
-    };
 
-    // Resolve combat
-    let (hit, damage_dealt) = resolve_combat(attacker_stats, defender_stats, &mut defender_health);
-
-    if hit {
-        // Log hit message
-        let message = format!(
-            "{} hits {} for {} damage! ({}/{} HP)",
-            attacker_name.0,
-            defender_name.0,
-            damage_dealt,
-            defender_health.current,
-            defender_health.max
+        // Sneaking up on a monster that hasn't noticed you pays off
+        let sneak_attack = attacker_sneaking && defender_ai.is_some_and(|ai| !ai.is_aware());
+
+        // Resolve combat
+        let (outcome, damage_dealt, resistance) = resolve_combat(
+            &attacker_stats,
+            defender_stats,
+            damage_type,
+            defender_resistances,
+            sneak_attack,
+            &mut defender_health,
+            &mut rng,
         );
-        combat_log.add_message(message);
 
-        // Check if enemy died
-        if defender_health.is_dead() {
-            let death_message = format!("{} dies!", defender_name.0);
-            combat_log.add_message(death_message);
+        if outcome == AttackOutcome::Miss {
+            missed_events.send(AttackMissed {
+                attacker: intent.attacker,
+                defender: intent.defender,
+                kind: intent.kind,
+                seq: sequence.advance(),
+            });
+            continue;
+        }
 
-            // Despawn enemy
-            commands.entity(target).despawn();
+        damage_events.send(DamageDealt {
+            source: Some(intent.attacker),
+            target: intent.defender,
+            amount: damage_dealt,
+            damage_type,
+            outcome,
+            resistance,
+            remaining: defender_health.current,
+            kind: Some(intent.kind),
+            seq: sequence.advance(),
+        });
+
+        if defender_health.is_dead() {
+            killed_events.send(EntityKilled {
+                entity: intent.defender,
+                killer: Some(intent.attacker),
+                seq: sequence.advance(),
+            });
         }
-    } else {
-        // Log miss message
-        let message = format!("{} misses {}!", attacker_name.0, defender_name.0);
-        combat_log.add_message(message);
     }
-
-    // Spend action point
-    action_points.spend(ATTACK_ACTION_COST);
 }
 
 // ============================================================================
 // COMBAT RESOLUTION
 // ============================================================================
 
-/// Roll for hit and calculate damage
+/// How an attack connected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttackOutcome {
+    Miss,
+    Glancing,
+    Hit,
+    Critical,
+}
+
+/// Chance (in percent, summing to 100) of each attack outcome
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HitDistribution {
+    pub miss: u32,
+    pub glancing: u32,
+    pub hit: u32,
+    pub critical: u32,
+}
+
+impl HitDistribution {
+    /// Total chance to connect in any way
+    pub fn chance_to_hit(&self) -> u32 {
+        self.glancing + self.hit + self.critical
+    }
+
+    /// Map a 0-99 roll onto an outcome (criticals first, misses last)
+    pub fn outcome_for_roll(&self, roll: u32) -> AttackOutcome {
+        if roll < self.critical {
+            AttackOutcome::Critical
+        } else if roll < self.critical + self.hit {
+            AttackOutcome::Hit
+        } else if roll < self.chance_to_hit() {
+            AttackOutcome::Glancing
+        } else {
+            AttackOutcome::Miss
+        }
+    }
+}
+
+/// Full outcome distribution for an attack (pure, no randomness)
+///
+/// Accuracy over evasion raises the hit and critical chances; evasion over
+/// accuracy lowers the hit chance and turns more hits into glancing blows.
+pub fn hit_distribution(attacker_stats: &CombatStats, defender_stats: &CombatStats) -> HitDistribution {
+    let edge = attacker_stats.accuracy - defender_stats.evasion;
+
+    let to_hit = (BASE_HIT_CHANCE + edge * HIT_CHANCE_PER_POINT)
+        .clamp(MIN_HIT_CHANCE, MAX_HIT_CHANCE);
+    let critical = (CRITICAL_HIT_CHANCE + edge.max(0)).min(to_hit);
+    let glancing = (GLANCING_BLOW_CHANCE + 2 * (-edge).max(0)).min(to_hit - critical);
+
+    HitDistribution {
+        miss: (100 - to_hit) as u32,
+        glancing: glancing as u32,
+        hit: (to_hit - critical - glancing) as u32,
+        critical: critical as u32,
+    }
+}
+
+/// Damage range (min, max) for an outcome before any randomness (pure)
+pub fn damage_range(
+    attacker_stats: &CombatStats,
+    defender_stats: &CombatStats,
+    outcome: AttackOutcome,
+) -> (i32, i32) {
+    let base_damage = attacker_stats.power - defender_stats.defense;
+    let scale = |damage: i32| -> i32 {
+        let scaled = match outcome {
+            AttackOutcome::Miss => return 0,
+            AttackOutcome::Glancing => damage as f32 * GLANCING_BLOW_MULTIPLIER,
+            AttackOutcome::Hit => damage as f32,
+            AttackOutcome::Critical => damage as f32 * CRITICAL_HIT_MULTIPLIER,
+        };
+        (scaled.round() as i32).max(1) // Minimum 1 damage
+    };
+
+    (
+        scale((base_damage - DAMAGE_VARIANCE).max(1)),
+        scale((base_damage + DAMAGE_VARIANCE).max(1)),
+    )
+}
+
+/// Roll for outcome and calculate damage after sneak bonus and resistances
 fn resolve_combat(
     attacker_stats: &CombatStats,
     defender_stats: &CombatStats,
+    damage_type: DamageType,
+    defender_resistances: Option<&Resistances>,
+    sneak_attack: bool,
     defender_health: &mut Health,
-) -> (bool, i32) {
-    // Roll for hit (0-99 vs BASE_HIT_CHANCE)
-    let roll = rand::random::<u32>() % 100;
-    let hit = roll < BASE_HIT_CHANCE;
+    rng: &mut GameRng,
+) -> (AttackOutcome, i32, Option<Resistance>) {
+    // Roll 0-99 against the outcome distribution
+    let distribution = hit_distribution(attacker_stats, defender_stats);
+    let outcome = distribution.outcome_for_roll(rng.gen_range(0..100));
 
-    if !hit {
-        return (false, 0);
+    if outcome == AttackOutcome::Miss {
+        return (outcome, 0, None);
     }
 
-    // Calculate base damage (power - defense)
-    let base_damage = attacker_stats.power - defender_stats.defense;
+    // Pick a value within the outcome's damage range
+    let (min_damage, max_damage) = damage_range(attacker_stats, defender_stats, outcome);
+    let mut rolled_damage = rng.gen_range(min_damage..=max_damage);
 
-    // Add variance (±DAMAGE_VARIANCE)
-    let variance = (rand::random::<i32>() % (DAMAGE_VARIANCE * 2 + 1)) - DAMAGE_VARIANCE;
-    let final_damage = (base_damage + variance).max(1); // Minimum 1 damage
+    if sneak_attack {
+        rolled_damage = (rolled_damage as f32 * SNEAK_ATTACK_MULTIPLIER).round() as i32;
+    }
+
+    // Scale by the defender's resistance to this damage type
+    let (final_damage, resistance) = match defender_resistances {
+        Some(resistances) => resistances.apply(rolled_damage, damage_type),
+        None => (rolled_damage, None),
+    };
 
     // Apply damage
     defender_health.take_damage(final_damage);
 
-    (true, final_damage)
+    (outcome, final_damage, resistance)
 }
 
 // ============================================================================
-// DEATH HANDLING SYSTEM
+// COMBAT LOG
 // ============================================================================
 
+/// Build typed combat log entries from this frame's combat events
+///
+/// The events are gathered from every queue and sorted by `seq`, so the log
+/// reads in the order things happened (a hit before the kill it caused).
+pub fn record_combat_events_system(
+    mut combat_log: ResMut<CombatLog>,
+    mut missed_events: EventReader<AttackMissed>,
+    mut damage_events: EventReader<DamageDealt>,
+    mut killed_events: EventReader<EntityKilled>,
+    mut heal_events: EventReader<HealApplied>,
+    name_query: Query<(&Name, Option<&Health>, Option<&Weapon>)>,
+) {
+    let name_of = |entity: Entity| -> String {
+        name_query
+            .get(entity)
+            .map(|(name, ..)| name.0.clone())
+            .unwrap_or_else(|_| "Something".to_string())
+    };
+    let max_health_of = |entity: Entity| -> i32 {
+        name_query
+            .get(entity)
+            .ok()
+            .and_then(|(_, health, _)| health.map(|h| h.max))
+            .unwrap_or(0)
+    };
+    let weapon_of = |entity: Entity| -> Option<String> {
+        name_query
+            .get(entity)
+            .ok()
+            .and_then(|(_, _, weapon)| weapon.map(|w| w.name.clone()))
+    };
+
+    // (seq, kind, text); one event may produce several lines
+    let mut entries: Vec<(u64, LogEntryKind, String)> = Vec::new();
+
+    for event in missed_events.read() {
+        let text = match event.kind {
+            AttackKind::Ranged => format!("{}'s shot misses {}!", name_of(event.attacker), name_of(event.defender)),
+            _ => format!("{} misses {}!", name_of(event.attacker), name_of(event.defender)),
+        };
+        entries.push((event.seq, LogEntryKind::Miss, text));
+    }
+
+    for event in damage_events.read() {
+        // Nothing to report but the immunity itself
+        if event.resistance == Some(Resistance::Immune) {
+            entries.push((
+                event.seq,
+                LogEntryKind::Damage,
+                format!("{} is immune to {}.", name_of(event.target), event.damage_type.name()),
+            ));
+            continue;
+        }
+
+        let damage = match event.damage_type {
+            DamageType::Physical => format!("{} damage", event.amount),
+            other => format!("{} {} damage", event.amount, other.name()),
+        };
+        let verb = match (event.outcome, event.kind) {
+            (AttackOutcome::Glancing, _) => "grazes",
+            (AttackOutcome::Critical, Some(AttackKind::Ranged)) => "critically shoots",
+            (AttackOutcome::Critical, _) => "critically hits",
+            (_, Some(AttackKind::Ranged)) => "shoots",
+            _ => "hits",
+        };
+        let text = match event.source {
+            Some(source) => {
+                // Spells are cast, not swung
+                let weapon = match (event.kind, weapon_of(source)) {
+                    (Some(AttackKind::Melee | AttackKind::Ranged), Some(weapon)) => format!(" with {}", weapon),
+                    _ => String::new(),
+                };
+                format!(
+                    "{} {} {}{} for {}! ({}/{} HP)",
+                    name_of(source),
+                    verb,
+                    name_of(event.target),
+                    weapon,
+                    damage,
+                    event.remaining,
+                    max_health_of(event.target),
+                )
+            }
+            None => format!(
+                "{} takes {}! ({}/{} HP)",
+                name_of(event.target),
+                damage,
+                event.remaining,
+                max_health_of(event.target),
+            ),
+        };
+        entries.push((event.seq, LogEntryKind::Damage, text));
+
+        let reaction = match event.resistance {
+            Some(Resistance::Resistant) => Some("resists"),
+            Some(Resistance::Vulnerable) => Some("is vulnerable to"),
+            _ => None,
+        };
+        if let Some(reaction) = reaction {
+            entries.push((
+                event.seq,
+                LogEntryKind::Damage,
+                format!("{} {} {}.", name_of(event.target), reaction, event.damage_type.name()),
+            ));
+        }
+    }
+
+    for event in killed_events.read() {
+        entries.push((event.seq, LogEntryKind::Kill, format!("{} dies!", name_of(event.entity))));
+    }
+
+    for event in heal_events.read() {
+        entries.push((
+            event.seq,
+            LogEntryKind::Heal,
+            format!("{} recovers {} HP.", name_of(event.target), event.amount),
+        ));
+    }
+
+    // Stable, so lines from the same event keep their order
+    entries.sort_by_key(|(seq, ..)| *seq);
+    for (_, kind, text) in entries {
+        combat_log.add_entry(kind, text);
+    }
+}
+
+// ============================================================================
+// DEATH HANDLING SYSTEMS
+// ============================================================================
+
+/// Despawn killed monsters (the player is left for check_player_death_system)
+pub fn remove_dead_entities_system(
+    mut commands: Commands,
+    mut killed_events: EventReader<EntityKilled>,
+    player_query: Query<(), With<Player>>,
+) {
+    for event in killed_events.read() {
+        if player_query.contains(event.entity) {
+            continue;
+        }
+
+        if let Some(entity_commands) = commands.get_entity(event.entity) {
+            entity_commands.despawn_recursive();
+        }
+    }
+}
+
 /// Check if player died and transition to GameOver
 pub fn check_player_death_system(
     player_query: Query<&Health, With<Player>>,
@@ -188,3 +626,83 @@ pub fn check_player_death_system(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(accuracy: i32, evasion: i32) -> CombatStats {
+        CombatStats::new(10, 2).with_accuracy(accuracy).with_evasion(evasion)
+    }
+
+    #[test]
+    fn outcome_chances_sum_to_100() {
+        for accuracy in -30..=30 {
+            for evasion in -30..=30 {
+                let d = hit_distribution(&stats(accuracy, 0), &stats(0, evasion));
+                assert_eq!(d.miss + d.glancing + d.hit + d.critical, 100, "accuracy {accuracy}, evasion {evasion}");
+            }
+        }
+    }
+
+    #[test]
+    fn accuracy_raises_and_evasion_lowers_hit_chance() {
+        let even = hit_distribution(&stats(5, 0), &stats(0, 5)).chance_to_hit();
+        let accurate = hit_distribution(&stats(7, 0), &stats(0, 5)).chance_to_hit();
+        let evasive = hit_distribution(&stats(5, 0), &stats(0, 7)).chance_to_hit();
+
+        assert!(accurate > even);
+        assert!(evasive < even);
+    }
+
+    #[test]
+    fn hit_chance_is_clamped() {
+        let hopeless = hit_distribution(&stats(0, 0), &stats(0, 100));
+        let certain = hit_distribution(&stats(100, 0), &stats(0, 0));
+
+        assert_eq!(hopeless.chance_to_hit(), MIN_HIT_CHANCE as u32);
+        assert_eq!(certain.chance_to_hit(), MAX_HIT_CHANCE as u32);
+    }
+
+    #[test]
+    fn extreme_rolls_crit_and_miss() {
+        for edge in [-100, -5, 0, 5, 100] {
+            let d = hit_distribution(&stats(edge, 0), &stats(0, 0));
+            assert_eq!(d.outcome_for_roll(0), AttackOutcome::Critical, "edge {edge}");
+            assert_eq!(d.outcome_for_roll(99), AttackOutcome::Miss, "edge {edge}");
+        }
+    }
+
+    #[test]
+    fn outcome_for_roll_follows_distribution() {
+        let d = hit_distribution(&stats(0, 0), &stats(0, 0));
+        let count = |outcome| (0..100).filter(|roll| d.outcome_for_roll(*roll) == outcome).count() as u32;
+
+        assert_eq!(count(AttackOutcome::Critical), d.critical);
+        assert_eq!(count(AttackOutcome::Hit), d.hit);
+        assert_eq!(count(AttackOutcome::Glancing), d.glancing);
+        assert_eq!(count(AttackOutcome::Miss), d.miss);
+    }
+
+    #[test]
+    fn damage_range_scales_with_outcome() {
+        // Power 10 against defense 2: 8 base damage, +/- DAMAGE_VARIANCE
+        let attacker = CombatStats::new(10, 0);
+        let defender = CombatStats::new(0, 2);
+
+        assert_eq!(damage_range(&attacker, &defender, AttackOutcome::Miss), (0, 0));
+        assert_eq!(damage_range(&attacker, &defender, AttackOutcome::Hit), (6, 10));
+        assert_eq!(damage_range(&attacker, &defender, AttackOutcome::Critical), (12, 20));
+        assert_eq!(damage_range(&attacker, &defender, AttackOutcome::Glancing), (3, 5));
+    }
+
+    #[test]
+    fn damage_range_never_drops_below_one() {
+        let weakling = CombatStats::new(1, 0);
+        let tank = CombatStats::new(0, 20);
+
+        assert_eq!(damage_range(&weakling, &tank, AttackOutcome::Glancing), (1, 1));
+        assert_eq!(damage_range(&weakling, &tank, AttackOutcome::Hit), (1, 1));
+        assert_eq!(damage_range(&weakling, &tank, AttackOutcome::Critical), (2, 2));
+    }
+}
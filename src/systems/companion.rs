@@ -0,0 +1,240 @@
+/// Companion systems - spawning, orders, targeting and the companion AI
+///
+/// Companions act during the monster phase of the turn, just before the
+/// monsters themselves.
+
+use bevy::prelude::*;
+use std::collections::HashSet;
+use crate::components::{
+    Player, Position, Viewshed, Health, CombatStats, DamageType, Weapon, Name, Renderable,
+    Faction, Grudges, AiTarget, Companion, CompanionCommand,
+};
+use crate::resources::{
+    CurrentMap, DijkstraMaps, CombatLog, FactionTable, Allegiance, ActionInput, PlayerAction,
+    grid_distance,
+};
+use crate::resources::dijkstra::NEIGHBOURS;
+use crate::systems::combat::{AttackIntent, AttackMissed, DamageDealt};
+use crate::systems::movement::EntityMoved;
+use crate::constants::*;
+
+// ============================================================================
+// SPAWNING
+// ============================================================================
+
+/// Spawn the player's starting pet on a free tile next to `near`, returning
+/// the tile it was placed on
+pub fn spawn_starting_pet(commands: &mut Commands, map: &CurrentMap, near: Position) -> Option<Position> {
+    let pos = NEIGHBOURS
+        .iter()
+        .map(|(dx, dy)| Position::new(near.x + dx, near.y + dy))
+        .find(|pos| map.is_walkable(pos.x, pos.y))?;
+
+    commands.spawn((
+        Companion::new(),
+        Faction::Player,
+        AiTarget::default(),
+        pos,
+        Name::new("Dog"),
+        Health::new(PET_STARTING_HEALTH),
+        CombatStats::new(PET_ATTACK_POWER, PET_DEFENSE)
+            .with_accuracy(PET_ACCURACY)
+            .with_evasion(PET_EVASION),
+        Weapon::new("Teeth", DamageType::Physical),
+        Renderable::new(COLOR_PET).with_tile(TILE_PET).with_glyph('d').with_size(0.8),
+        Viewshed::new(PET_FOV_RADIUS),
+    ));
+
+    info!("Pet spawned at ({}, {})", pos.x, pos.y);
+    Some(pos)
+}
+
+// ============================================================================
+// ORDERS
+// ============================================================================
+
+/// Cycle companion orders (V by default): follow -> stay -> attack -> follow
+pub fn companion_command_input_system(
+    actions: Res<ActionInput>,
+    mut combat_log: ResMut<CombatLog>,
+    mut companion_query: Query<(&Position, &Name, &mut Companion, &mut AiTarget)>,
+) {
+    if !actions.just_pressed(PlayerAction::CommandPet) {
+        return;
+    }
+
+    for (pos, name, mut companion, mut target) in companion_query.iter_mut() {
+        companion.command = match companion.command {
+            CompanionCommand::Follow => CompanionCommand::Stay { at: *pos },
+            CompanionCommand::Stay { .. } => CompanionCommand::Attack,
+            CompanionCommand::Attack => CompanionCommand::Follow,
+        };
+
+        // Fresh orders replace the current fight when coming back to heel
+        if companion.command == CompanionCommand::Follow {
+            target.0 = None;
+        }
+
+        combat_log.add_message(format!("{} {}.", name.0, companion.command.describe()));
+    }
+}
+
+// ============================================================================
+// TARGETING
+// ============================================================================
+
+/// Point companions at whatever the player attacks, and at anything that
+/// attacks the player or a companion
+///
+/// Companions told to stay only take up fights they are dragged into.
+pub fn companion_target_system(
+    mut missed_events: EventReader<AttackMissed>,
+    mut damage_events: EventReader<DamageDealt>,
+    player_query: Query<Entity, With<Player>>,
+    mut companion_query: Query<(Entity, &Companion, &mut AiTarget)>,
+) {
+    let Ok(player) = player_query.get_single() else {
+        return;
+    };
+
+    let attacks = missed_events
+        .read()
+        .map(|event| (event.attacker, event.defender))
+        .chain(damage_events.read().filter_map(|event| event.source.map(|source| (source, event.target))))
+        .collect::<Vec<_>>();
+
+    let companions: HashSet<Entity> = companion_query.iter().map(|(entity, ..)| entity).collect();
+    let on_our_side = |entity: Entity| entity == player || companions.contains(&entity);
+
+    for (attacker, defender) in attacks {
+        if on_our_side(attacker) && on_our_side(defender) {
+            continue;
+        }
+
+        for (companion, orders, mut target) in companion_query.iter_mut() {
+            let staying = matches!(orders.command, CompanionCommand::Stay { .. });
+            if defender == companion {
+                // Fight back against whoever bites first
+                target.0 = Some(attacker);
+            } else if staying {
+                continue;
+            } else if attacker == player {
+                // The player picks the fight
+                target.0 = Some(defender);
+            } else if on_our_side(defender) && target.0.is_none() {
+                target.0 = Some(attacker);
+            }
+        }
+    }
+}
+
+// ============================================================================
+// COMPANION AI
+// ============================================================================
+
+/// Move and fight with every companion according to its orders
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn companion_action_system(
+    map: Res<CurrentMap>,
+    mut dijkstra_maps: ResMut<DijkstraMaps>,
+    factions: Res<FactionTable>,
+    player_query: Query<&Position, (With<Player>, Without<Companion>)>,
+    mut companion_query: Query<
+        (Entity, &mut Position, &Health, &Viewshed, &Faction, &Companion, &mut AiTarget),
+        Without<Player>,
+    >,
+    actor_query: Query<(Entity, &Position, &Faction, Option<&Grudges>, &Health), (Without<Companion>, Without<Player>)>,
+    mut attack_intents: EventWriter<AttackIntent>,
+    mut moved_events: EventWriter<EntityMoved>,
+) {
+    let Ok(player_pos) = player_query.get_single() else {
+        return;
+    };
+    let player_pos = *player_pos;
+
+    // Tiles taken by actors; updated as companions move so they don't stack
+    let mut occupied: HashSet<Position> = actor_query.iter().map(|(_, pos, ..)| *pos).collect();
+    occupied.extend(companion_query.iter().map(|(_, pos, ..)| *pos));
+    occupied.insert(player_pos);
+
+    for (companion, mut pos, health, viewshed, faction, orders, mut target) in companion_query.iter_mut() {
+        if health.is_dead() {
+            continue;
+        }
+
+        // Forget targets that died, or that a following companion shouldn't chase
+        let mut target_pos = target
+            .0
+            .and_then(|target| actor_query.get(target).ok())
+            .filter(|(.., target_health)| !target_health.is_dead())
+            .map(|(_, target_pos, ..)| *target_pos);
+        if orders.command == CompanionCommand::Follow
+            && target_pos.is_some_and(|target_pos| grid_distance(player_pos, target_pos) > COMPANION_LEASH_DISTANCE)
+        {
+            target_pos = None;
+        }
+        if target_pos.is_none() {
+            target.0 = None;
+        }
+
+        // On the attack: pick the nearest hostile in view
+        if orders.command == CompanionCommand::Attack && target.0.is_none() {
+            let me = Allegiance::new(companion, faction, None);
+            if let Some((foe, foe_pos, ..)) = actor_query
+                .iter()
+                .filter(|(other, other_pos, other_faction, other_grudges, other_health)| {
+                    !other_health.is_dead()
+                        && viewshed.can_see(other_pos)
+                        && factions.is_hostile(me, Allegiance::new(*other, other_faction, *other_grudges))
+                })
+                .min_by_key(|(_, other_pos, ..)| grid_distance(*pos, **other_pos))
+            {
+                target.0 = Some(foe);
+                target_pos = Some(*foe_pos);
+            }
+        }
+
+        let too_far = grid_distance(*pos, player_pos) > COMPANION_LEASH_DISTANCE;
+
+        let step = match (orders.command, target.0.zip(target_pos)) {
+            // Anything adjacent that we're fighting gets bitten
+            (_, Some((foe, foe_pos))) if grid_distance(*pos, foe_pos) <= 1 => {
+                attack_intents.send(AttackIntent::melee(companion, foe));
+                None
+            }
+            (CompanionCommand::Stay { at }, _) if *pos != at => {
+                dijkstra_maps.towards(&map, at).downhill_step(&map, *pos, |p| occupied.contains(&p))
+            }
+            (CompanionCommand::Stay { .. }, _) => None,
+            (CompanionCommand::Follow, _) if too_far => player_step(&map, &dijkstra_maps, *pos, &occupied),
+            (_, Some((_, foe_pos))) => {
+                dijkstra_maps.towards(&map, foe_pos).downhill_step(&map, *pos, |p| occupied.contains(&p))
+            }
+            (_, None) if grid_distance(*pos, player_pos) > COMPANION_FOLLOW_DISTANCE => {
+                player_step(&map, &dijkstra_maps, *pos, &occupied)
+            }
+            (_, None) => None,
+        };
+
+        if let Some(step) = step {
+            let from = *pos;
+            occupied.remove(&from);
+            occupied.insert(step);
+            *pos = step;
+            moved_events.send(EntityMoved { entity: companion, from, to: step });
+        }
+    }
+}
+
+/// Step towards the player along the shared approach map
+fn player_step(
+    map: &CurrentMap,
+    dijkstra_maps: &DijkstraMaps,
+    from: Position,
+    occupied: &HashSet<Position>,
+) -> Option<Position> {
+    dijkstra_maps
+        .approach
+        .as_ref()
+        .and_then(|approach| approach.downhill_step(map, from, |p| occupied.contains(&p)))
+}
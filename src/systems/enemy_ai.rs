@@ -1,21 +1,595 @@
-/// Enemy AI system (Phase 5 implementation)
-/// Currently a stub placeholder
+/// Enemy AI systems
+/// Monsters follow a simple behaviour state machine (see `AiState`) and,
+/// when hunting, fight according to their `MonsterBehaviour` archetype.
+/// Their targets are whichever actors the faction table makes them hostile to.
 
 use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+use rand::Rng;
+use crate::components::{
+    Player, Position, Viewshed, Health, Enemy, AiState, AiTarget, GuardPost,
+    MonsterBehaviour, Spellbook, SpellKind, SummonedBy, Name,
+    PackMember, PackLeader, Routed, Faction, Reaction, Grudges, Boss,
+};
+use crate::resources::{
+    CurrentMap, DijkstraMaps, CombatLog, FactionTable, Allegiance, GameRng,
+    grid_distance, line_between,
+};
+use crate::resources::dijkstra::NEIGHBOURS;
+use crate::systems::combat::{AttackIntent, EntityKilled};
+use crate::systems::movement::EntityMoved;
+use crate::systems::spells::CastSpell;
+use crate::constants::{
+    AI_WAKE_CHANCE, AI_FLEE_HEALTH_THRESHOLD, AI_WANDER_RADIUS, AI_WANDER_MOVE_CHANCE,
+    AI_HEAL_ALLY_THRESHOLD, Z_LAYER_UI, TILE_SIZE,
+};
 
 // ============================================================================
-// ENEMY AI STUB
+// RESOURCES & COMPONENTS
 // ============================================================================
 
-/// Process enemy actions during their turn
-/// TODO (Phase 5): Implement pathfinding, attack logic, etc.
-pub fn enemy_action_system() {
-    // Stub: Will be expanded in Phase 5
-    // Future implementation will:
-    // - Query all enemies with Position and AI components
-    // - Calculate pathfinding to player
-    // - Execute movement or attack actions
-    // - Consume enemy action points
+/// Whether the AI state debug overlay is shown (toggle with F3)
+#[derive(Resource, Default)]
+pub struct AiDebugOverlay(pub bool);
 
-    info!("Enemies act (stub - Phase 5 will implement full AI)");
+/// Text label above a monster showing its AI state
+#[derive(Component)]
+pub struct AiStateLabel;
+
+// ============================================================================
+// STATE TRANSITIONS
+// ============================================================================
+
+/// Update each monster's AI state and target from what it can see and its health
+///
+/// A monster's target is the nearest hostile actor in view, as decided by the
+/// faction table. Pack members share awareness: if any awake member sees a
+/// foe, the whole pack knows where it is.
+#[allow(clippy::type_complexity)]
+pub fn update_ai_state_system(
+    factions: Res<FactionTable>,
+    mut rng: ResMut<GameRng>,
+    actor_query: Query<(Entity, &Position, &Faction, Option<&Grudges>, &Health)>,
+    mut enemy_query: Query<
+        (
+            Entity,
+            &Position,
+            &Viewshed,
+            &Health,
+            &GuardPost,
+            &Faction,
+            Option<&Grudges>,
+            &mut AiState,
+            &mut AiTarget,
+            Option<&PackMember>,
+            Option<&Routed>,
+            Option<&Boss>,
+        ),
+        With<Enemy>,
+    >,
+) {
+    // Nearest living hostile actor each monster can see
+    let sightings: HashMap<Entity, (Entity, Position)> = enemy_query
+        .iter()
+        .filter_map(|(entity, pos, viewshed, _, _, faction, grudges, ..)| {
+            let me = Allegiance::new(entity, faction, grudges);
+            actor_query
+                .iter()
+                .filter(|(other, other_pos, other_faction, other_grudges, other_health)| {
+                    !other_health.is_dead()
+                        && viewshed.can_see(other_pos)
+                        && factions.is_hostile(me, Allegiance::new(*other, other_faction, *other_grudges))
+                })
+                .min_by_key(|(_, other_pos, ..)| grid_distance(*pos, **other_pos))
+                .map(|(other, other_pos, ..)| (entity, (other, *other_pos)))
+        })
+        .collect();
+
+    // Foes spotted by an awake member of each pack
+    let pack_sightings: HashMap<Entity, (Entity, Position)> = enemy_query
+        .iter()
+        .filter(|(_, _, _, _, _, _, _, state, ..)| **state != AiState::Asleep)
+        .filter_map(|(entity, .., pack, _, _)| {
+            let sighting = sightings.get(&entity)?;
+            pack.map(|pack| (pack.leader, *sighting))
+        })
+        .collect();
+
+    for (entity, pos, _, health, post, _, _, mut state, mut target, pack, routed, boss) in enemy_query.iter_mut() {
+        let pack_sighting = pack.and_then(|pack| pack_sightings.get(&pack.leader)).copied();
+        let foe = sightings.get(&entity).copied().or(pack_sighting);
+        // Bosses fight to the death
+        let badly_hurt = boss.is_none() && (routed.is_some() || health.percentage() < AI_FLEE_HEALTH_THRESHOLD);
+
+        let next = match (*state, foe) {
+            // The pack raises the alarm and wakes its sleepers
+            (AiState::Asleep, Some((_, foe_pos))) if pack_sighting.is_some() => {
+                AiState::Hunting { last_known: foe_pos }
+            }
+            // Sleepers only sometimes notice a foe in view
+            (AiState::Asleep, Some((_, foe_pos))) if rng.gen_range(0..100) < AI_WAKE_CHANCE => {
+                AiState::Hunting { last_known: foe_pos }
+            }
+            (AiState::Asleep, _) => AiState::Asleep,
+            (AiState::Wandering | AiState::Returning | AiState::Investigating { .. }, Some((_, foe_pos))) => {
+                AiState::Hunting { last_known: foe_pos }
+            }
+            (AiState::Investigating { target }, None) if *pos == target => AiState::Returning,
+            (AiState::Returning, None) if *pos == post.0 => AiState::Wandering,
+            (AiState::Hunting { .. }, Some(_)) if badly_hurt => AiState::Fleeing,
+            (AiState::Hunting { .. }, Some((_, foe_pos))) => AiState::Hunting { last_known: foe_pos },
+            (AiState::Hunting { last_known }, None) if *pos == last_known => AiState::Returning,
+            (AiState::Fleeing, None) => AiState::Returning,
+            (other, _) => other,
+        };
+        let next_target = AiTarget(foe.filter(|_| next.is_aware()).map(|(foe, _)| foe));
+
+        // Only write on change to keep change detection meaningful
+        if *state != next {
+            *state = next;
+        }
+        if *target != next_target {
+            *target = next_target;
+        }
+    }
+}
+
+/// Break up packs whose leader was killed; the survivors flee together
+pub fn break_leaderless_packs_system(
+    mut commands: Commands,
+    mut killed_events: EventReader<EntityKilled>,
+    leader_query: Query<&Name, With<PackLeader>>,
+    mut member_query: Query<(Entity, &PackMember, &mut AiState), Without<PackLeader>>,
+    mut combat_log: ResMut<CombatLog>,
+) {
+    for killed in killed_events.read() {
+        let Ok(leader_name) = leader_query.get(killed.entity) else {
+            continue;
+        };
+
+        let mut scattered = 0;
+        for (member, pack, mut state) in member_query.iter_mut() {
+            if pack.leader != killed.entity {
+                continue;
+            }
+            commands.entity(member).remove::<PackMember>().insert(Routed);
+            if *state != AiState::Asleep {
+                *state = AiState::Fleeing;
+            }
+            scattered += 1;
+        }
+
+        if scattered > 0 {
+            combat_log.add_message(format!("With {} dead, the pack scatters!", leader_name.0));
+        }
+    }
+}
+
+// ============================================================================
+// ENEMY ACTIONS
+// ============================================================================
+
+/// Query data for a monster taking its turn
+type MonsterActor = (
+    Entity,
+    &'static mut Position,
+    &'static Health,
+    &'static Viewshed,
+    &'static GuardPost,
+    &'static Faction,
+    &'static AiState,
+    &'static AiTarget,
+    &'static MonsterBehaviour,
+    Option<&'static mut Spellbook>,
+    Option<&'static PackMember>,
+);
+
+/// What a hunting monster decided to do this turn
+enum HuntAction {
+    Step(Option<Position>),
+    Attack,
+    Shoot,
+    Cast(usize, Entity),
+}
+
+/// Process enemy actions during their turn according to their AI state
+/// and, once hunting, their behaviour archetype
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn enemy_action_system(
+    player_query: Query<(Entity, &Position), (With<Player>, Without<Enemy>)>,
+    mut enemy_query: Query<MonsterActor, With<Enemy>>,
+    other_actors: Query<(Entity, &Position), (With<Faction>, Without<Enemy>)>,
+    minion_query: Query<&SummonedBy>,
+    map: Res<CurrentMap>,
+    mut dijkstra_maps: ResMut<DijkstraMaps>,
+    factions: Res<FactionTable>,
+    mut combat_log: ResMut<CombatLog>,
+    mut attack_intents: EventWriter<AttackIntent>,
+    mut cast_events: EventWriter<CastSpell>,
+    mut moved_events: EventWriter<EntityMoved>,
+    mut rng: ResMut<GameRng>,
+) {
+    let (player, player_pos) = match player_query.get_single() {
+        Ok(data) => data,
+        Err(_) => return,
+    };
+
+    // Where every actor stands; updated as enemies move so they don't stack
+    let mut positions: HashMap<Entity, Position> = enemy_query
+        .iter()
+        .map(|(entity, pos, ..)| (entity, *pos))
+        .collect();
+    positions.extend(other_actors.iter().map(|(entity, pos)| (entity, *pos)));
+    positions.insert(player, *player_pos);
+    let mut occupied: HashSet<Position> = positions.values().copied().collect();
+
+    // Tiles next to each target already claimed by a pack member this turn
+    let mut claimed: HashSet<Position> = HashSet::new();
+
+    // Snapshot of monsters for spell targeting (healing allies)
+    let allies: Vec<(Entity, Position, f32, Faction)> = enemy_query
+        .iter()
+        .filter(|(_, _, health, ..)| !health.is_dead())
+        .map(|(entity, pos, health, _, _, faction, ..)| (entity, *pos, health.percentage(), *faction))
+        .collect();
+
+    for (enemy, mut enemy_pos, health, viewshed, post, faction, state, target, behaviour, mut spellbook, pack) in
+        enemy_query.iter_mut()
+    {
+        if health.is_dead() {
+            continue;
+        }
+
+        if let Some(spellbook) = spellbook.as_mut() {
+            spellbook.tick();
+        }
+
+        let target = target.0.and_then(|target| positions.get(&target).map(|pos| (target, *pos)));
+
+        let step = match (*state, target) {
+            (AiState::Asleep, _) => None,
+            (AiState::Hunting { last_known }, None) => {
+                dijkstra_maps
+                    .towards(&map, last_known)
+                    .downhill_step(&map, *enemy_pos, |p| occupied.contains(&p))
+            }
+            (AiState::Hunting { .. }, Some((target, target_pos))) => {
+                let adjacent = grid_distance(*enemy_pos, target_pos) <= 1;
+
+                // Pack brawlers spread out around their target rather than queueing
+                let surround_tile = match behaviour {
+                    MonsterBehaviour::Melee | MonsterBehaviour::Caster if pack.is_some() && !adjacent => {
+                        claim_surround_tile(&map, *enemy_pos, target_pos, &occupied, &mut claimed)
+                    }
+                    _ => None,
+                };
+
+                let mut context = HuntContext {
+                    map: &map,
+                    dijkstra_maps: &mut dijkstra_maps,
+                    factions: &factions,
+                    occupied: &occupied,
+                    me: enemy,
+                    faction: *faction,
+                    pos: *enemy_pos,
+                    viewshed,
+                    target,
+                    target_pos,
+                    target_is_player: target == player,
+                    allies: &allies,
+                    minion_count: minion_query.iter().filter(|summoner| summoner.0 == enemy).count(),
+                    surround_tile,
+                };
+
+                match choose_hunt_action(&mut context, behaviour, spellbook.as_deref()) {
+                    HuntAction::Step(step) => step,
+                    HuntAction::Attack => {
+                        attack_intents.send(AttackIntent::melee(enemy, target));
+                        None
+                    }
+                    HuntAction::Shoot => {
+                        if target == player {
+                            combat_log.add_message("An arrow flies from the shadows!".to_string());
+                        }
+                        attack_intents.send(AttackIntent::ranged(enemy, target));
+                        None
+                    }
+                    HuntAction::Cast(idx, spell_target) => {
+                        if let Some(spellbook) = spellbook.as_mut() {
+                            let spell = spellbook.spells[idx].spell;
+                            spellbook.start_cooldown(idx);
+                            cast_events.send(CastSpell { caster: enemy, spell, target: spell_target });
+                        }
+                        None
+                    }
+                }
+            }
+            (AiState::Fleeing, target) => {
+                // The flee map leads away from the player
+                let flee_step = dijkstra_maps
+                    .flee
+                    .as_ref()
+                    .and_then(|flee| flee.downhill_step(&map, *enemy_pos, |p| occupied.contains(&p)));
+
+                // Cornered: fight back
+                if let Some((target, target_pos)) = target {
+                    if flee_step.is_none() && grid_distance(*enemy_pos, target_pos) <= 1 {
+                        attack_intents.send(AttackIntent::melee(enemy, target));
+                    }
+                }
+                flee_step
+            }
+            (AiState::Investigating { target }, _) => {
+                dijkstra_maps
+                    .towards(&map, target)
+                    .downhill_step(&map, *enemy_pos, |p| occupied.contains(&p))
+            }
+            (AiState::Returning, _) => {
+                dijkstra_maps
+                    .towards(&map, post.0)
+                    .downhill_step(&map, *enemy_pos, |p| occupied.contains(&p))
+            }
+            (AiState::Wandering, _) => wander_step(&map, *enemy_pos, post.0, &occupied, &mut rng),
+        };
+
+        if let Some(step) = step {
+            let from = *enemy_pos;
+            occupied.remove(&from);
+            occupied.insert(step);
+            positions.insert(enemy, step);
+            *enemy_pos = step;
+            moved_events.send(EntityMoved { entity: enemy, from, to: step });
+        }
+    }
+}
+
+/// Everything a hunting monster considers when picking an action
+struct HuntContext<'a> {
+    map: &'a CurrentMap,
+    dijkstra_maps: &'a mut DijkstraMaps,
+    factions: &'a FactionTable,
+    occupied: &'a HashSet<Position>,
+    me: Entity,
+    faction: Faction,
+    pos: Position,
+    viewshed: &'a Viewshed,
+    target: Entity,
+    target_pos: Position,
+    /// The shared approach and flee maps only lead to/from the player
+    target_is_player: bool,
+    allies: &'a [(Entity, Position, f32, Faction)],
+    minion_count: usize,
+    /// Free tile next to the target this pack member is heading for
+    surround_tile: Option<Position>,
+}
+
+impl HuntContext<'_> {
+    fn distance_to_target(&self) -> i32 {
+        grid_distance(self.pos, self.target_pos)
+    }
+
+    /// In view, in range, and with no walls or bodies in the way
+    fn has_shot_at_target(&self, range: i32) -> bool {
+        self.viewshed.can_see(&self.target_pos)
+            && self.distance_to_target() <= range
+            && self.map.has_clear_line(self.pos, self.target_pos)
+            && line_between(self.pos, self.target_pos)
+                .iter()
+                .all(|tile| *tile == self.target_pos || !self.occupied.contains(tile))
+    }
+
+    /// Step towards the target: the shared approach map for the player,
+    /// this turn's goal map for anyone else
+    fn approach_step(&mut self) -> Option<Position> {
+        let goal_map = if self.target_is_player {
+            self.dijkstra_maps.approach.as_ref()
+        } else {
+            Some(self.dijkstra_maps.towards(self.map, self.target_pos))
+        };
+        goal_map.and_then(|goal_map| goal_map.downhill_step(self.map, self.pos, |p| self.occupied.contains(&p)))
+    }
+
+    /// Step away from the target (only the player has a flee map)
+    fn retreat_step(&self) -> Option<Position> {
+        if !self.target_is_player {
+            return None;
+        }
+        self.dijkstra_maps
+            .flee
+            .as_ref()
+            .and_then(|flee| flee.downhill_step(self.map, self.pos, |p| self.occupied.contains(&p)))
+    }
+
+    /// Walk in to melee range, or hit if already there
+    fn melee(&mut self) -> HuntAction {
+        if self.distance_to_target() <= 1 {
+            return HuntAction::Attack;
+        }
+
+        let surround_step = self.surround_tile.and_then(|tile| {
+            self.dijkstra_maps
+                .towards(self.map, tile)
+                .downhill_step(self.map, self.pos, |p| self.occupied.contains(&p))
+        });
+        HuntAction::Step(surround_step.or_else(|| self.approach_step()))
+    }
+
+    /// Back off to roughly `preferred_distance`, fighting if cornered
+    fn keep_distance(&mut self, preferred_distance: i32) -> HuntAction {
+        let distance = self.distance_to_target();
+        if distance < preferred_distance {
+            match self.retreat_step() {
+                Some(step) => HuntAction::Step(Some(step)),
+                None if distance <= 1 => HuntAction::Attack,
+                None => HuntAction::Step(None),
+            }
+        } else if distance > preferred_distance + 2 {
+            HuntAction::Step(self.approach_step())
+        } else {
+            HuntAction::Step(None)
+        }
+    }
+
+    /// First ready spell whose conditions are met, with its target
+    fn pick_spell(&self, spellbook: &Spellbook) -> Option<(usize, Entity)> {
+        spellbook.ready().find_map(|(idx, spell)| {
+            let target = match spell.kind {
+                SpellKind::Bolt { .. } => {
+                    self.has_shot_at_target(spell.range).then_some(self.target)
+                }
+                SpellKind::HealAlly { .. } => self
+                    .allies
+                    .iter()
+                    .filter(|(ally, pos, percentage, ally_faction)| {
+                        *ally != self.me
+                            && self.factions.reaction(self.faction, *ally_faction) == Reaction::Friendly
+                            && *percentage < AI_HEAL_ALLY_THRESHOLD
+                            && grid_distance(self.pos, *pos) <= spell.range
+                            && self.viewshed.can_see(pos)
+                    })
+                    .map(|(ally, ..)| *ally)
+                    .next(),
+                SpellKind::Blink => (self.distance_to_target() <= 1).then_some(self.target),
+                SpellKind::Nova { radius, .. } => (self.distance_to_target() <= radius).then_some(self.target),
+                SpellKind::Summon { max_minions, .. } => {
+                    (self.viewshed.can_see(&self.target_pos) && self.minion_count < max_minions)
+                        .then_some(self.target)
+                }
+            };
+            target.map(|target| (idx, target))
+        })
+    }
+}
+
+/// Decide a hunting monster's action from its behaviour archetype
+fn choose_hunt_action(
+    context: &mut HuntContext,
+    behaviour: &MonsterBehaviour,
+    spellbook: Option<&Spellbook>,
+) -> HuntAction {
+    // Spells take priority whenever one is ready and useful
+    if let Some((idx, target)) = spellbook.and_then(|book| context.pick_spell(book)) {
+        return HuntAction::Cast(idx, target);
+    }
+
+    match *behaviour {
+        MonsterBehaviour::Melee | MonsterBehaviour::Caster => context.melee(),
+        MonsterBehaviour::Archer { range, preferred_distance } => {
+            let too_close = context.distance_to_target() < preferred_distance;
+            if too_close {
+                if let Some(step) = context.retreat_step() {
+                    return HuntAction::Step(Some(step));
+                }
+            }
+            if context.has_shot_at_target(range) {
+                HuntAction::Shoot
+            } else {
+                HuntAction::Step(context.approach_step())
+            }
+        }
+        MonsterBehaviour::Summoner { preferred_distance } => context.keep_distance(preferred_distance),
+    }
+}
+
+/// Claim the nearest free tile next to the target that no pack member has
+/// claimed yet this turn
+fn claim_surround_tile(
+    map: &CurrentMap,
+    from: Position,
+    target_pos: Position,
+    occupied: &HashSet<Position>,
+    claimed: &mut HashSet<Position>,
+) -> Option<Position> {
+    let tile = NEIGHBOURS
+        .iter()
+        .map(|(dx, dy)| Position::new(target_pos.x + dx, target_pos.y + dy))
+        .filter(|pos| map.is_walkable(pos.x, pos.y) && !occupied.contains(pos) && !claimed.contains(pos))
+        .min_by_key(|pos| grid_distance(from, *pos))?;
+
+    claimed.insert(tile);
+    Some(tile)
+}
+
+/// Random step that stays within AI_WANDER_RADIUS of the post
+fn wander_step(
+    map: &CurrentMap,
+    from: Position,
+    post: Position,
+    occupied: &HashSet<Position>,
+    rng: &mut GameRng,
+) -> Option<Position> {
+    if rng.gen_range(0..100) >= AI_WANDER_MOVE_CHANCE {
+        return None;
+    }
+
+    let options: Vec<Position> = NEIGHBOURS
+        .iter()
+        .map(|(dx, dy)| Position::new(from.x + dx, from.y + dy))
+        .filter(|pos| map.is_walkable(pos.x, pos.y) && !occupied.contains(pos))
+        .filter(|pos| (pos.x - post.x).abs() <= AI_WANDER_RADIUS && (pos.y - post.y).abs() <= AI_WANDER_RADIUS)
+        .collect();
+
+    if options.is_empty() {
+        return None;
+    }
+    Some(options[rng.gen_range(0..options.len())])
+}
+
+// ============================================================================
+// DEBUG OVERLAY
+// ============================================================================
+
+/// Toggle the AI state overlay with F3
+pub fn toggle_ai_debug_overlay_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<AiDebugOverlay>,
+) {
+    if keyboard.just_pressed(KeyCode::F3) {
+        overlay.0 = !overlay.0;
+        info!("AI debug overlay {}", if overlay.0 { "enabled" } else { "disabled" });
+    }
+}
+
+/// Keep a state label above every monster while the overlay is enabled
+///
+/// Labels are children of the monster, so they inherit its FOV visibility.
+pub fn update_ai_debug_overlay_system(
+    mut commands: Commands,
+    overlay: Res<AiDebugOverlay>,
+    enemy_query: Query<(Entity, &AiState, Option<&Children>), With<Enemy>>,
+    mut label_query: Query<(&mut Text2d, &mut Visibility), With<AiStateLabel>>,
+) {
+    for (enemy, state, children) in enemy_query.iter() {
+        let existing = children
+            .and_then(|children| children.iter().find(|child| label_query.contains(**child)).copied());
+
+        match existing {
+            Some(label) => {
+                let Ok((mut text, mut visibility)) = label_query.get_mut(label) else {
+                    continue;
+                };
+                if text.0 != state.label() {
+                    text.0 = state.label().to_string();
+                }
+                let target = if overlay.0 { Visibility::Inherited } else { Visibility::Hidden };
+                if *visibility != target {
+                    *visibility = target;
+                }
+            }
+            None if overlay.0 => {
+                commands.entity(enemy).with_children(|parent| {
+                    parent.spawn((
+                        AiStateLabel,
+                        Text2d::new(state.label()),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        Transform::from_xyz(0.0, TILE_SIZE * 0.75, Z_LAYER_UI),
+                    ));
+                });
+            }
+            None => {}
+        }
+    }
 }
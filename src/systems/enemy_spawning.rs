@@ -1,57 +1,71 @@
 /// Enemy spawning system
 
 use bevy::prelude::*;
-use crate::components::{Enemy, Position, Name, Health, CombatStats, Renderable, Viewshed};
-use crate::resources::CurrentMap;
+use std::collections::HashSet;
+use rand::Rng;
+use crate::components::{
+    Enemy, Position, Name, Health, CombatStats, Resistances, Weapon,
+    Renderable, Viewshed, AiState, AiTarget, GuardPost, Spellbook, PackMember, PackLeader, Boss,
+};
+use crate::resources::{CurrentMap, Bestiary, MonsterTemplate, GameRng, grid_distance};
 use crate::constants::*;
 
 // ============================================================================
 // ENEMY SPAWNING
 // ============================================================================
 
-/// Spawn 3-5 enemies at random walkable positions
+/// Spawn 3-5 enemies at random walkable positions outside the boss arena,
+/// picked from the bestiary
+///
+/// Some spawns bring a pack of followers of the same kind along. Tiles in
+/// `occupied` (the player and their companions) are left free.
 pub fn spawn_enemies_system(
     mut commands: Commands,
     map: &CurrentMap,
+    bestiary: &Bestiary,
+    occupied: &HashSet<Position>,
+    rng: &mut GameRng,
 ) {
     // Random count between ENEMY_MIN_COUNT and ENEMY_MAX_COUNT
-    let count = (rand::random::<usize>() % (ENEMY_MAX_COUNT - ENEMY_MIN_COUNT + 1)) + ENEMY_MIN_COUNT;
+    let count = rng.gen_range(ENEMY_MIN_COUNT..=ENEMY_MAX_COUNT);
 
     info!("Spawning {} enemies", count);
 
+    // Tiles already taken, so no two actors share one
+    let mut occupied = occupied.clone();
+
     for i in 0..count {
+        let Some(template) = bestiary.random_spawnable(rng) else {
+            warn!("Bestiary has no spawnable monsters");
+            return;
+        };
+
         let mut attempts = 0;
 
         loop {
             // Random position
-            let x = rand::random::<usize>() % map.width;
-            let y = rand::random::<usize>() % map.height;
-
-            // Check: walkable, not player spawn position (10, 10)
-            if map.is_walkable(x as i32, y as i32) && (x as i32 != 10 || y as i32 != 10) {
-                // Spawn enemy
-                commands.spawn((
-                    Enemy,
-                    Position::new(x as i32, y as i32),
-                    Name::new(format!("Goblin #{}", i + 1)),
-                    Health::new(ENEMY_STARTING_HEALTH),
-                    CombatStats::new(ENEMY_ATTACK_POWER, ENEMY_DEFENSE),
-                    Renderable::new(COLOR_ENEMY),
-                    Viewshed::new(ENEMY_FOV_RADIUS),
-                    Sprite {
-                        color: COLOR_ENEMY,
-                        custom_size: Some(Vec2::new(TILE_SIZE, TILE_SIZE)),
-                        ..default()
-                    },
-                    Transform::from_xyz(
-                        x as f32 * TILE_SIZE,
-                        y as f32 * TILE_SIZE,
-                        Z_LAYER_CHARACTERS,
-                    ),
-                    Visibility::Hidden, // FOV system will reveal
-                ));
-
-                info!("Spawned Goblin #{} at ({}, {})", i + 1, x, y);
+            let x = rng.gen_range(0..map.width);
+            let y = rng.gen_range(0..map.height);
+
+            // Check: walkable, not taken and outside the boss arena
+            let pos = Position::new(x as i32, y as i32);
+            if map.is_walkable(pos.x, pos.y) && !occupied.contains(&pos) && !map.in_arena(pos) {
+                // Some monsters start asleep, the rest wander around their post
+                let initial_state = if rng.gen_range(0..100) < AI_START_ASLEEP_CHANCE {
+                    AiState::Asleep
+                } else {
+                    AiState::Wandering
+                };
+
+                let name = format!("{} #{}", template.name, i + 1);
+                let monster = spawn_monster(&mut commands, template, pos, name, initial_state);
+                occupied.insert(pos);
+
+                info!("Spawned {} #{} at ({}, {})", template.name, i + 1, x, y);
+
+                if rng.gen_range(0..100) < PACK_SPAWN_CHANCE {
+                    spawn_pack_followers(&mut commands, map, template, monster, pos, &mut occupied, i + 1, initial_state, rng);
+                }
                 break;
             }
 
@@ -63,3 +77,81 @@ pub fn spawn_enemies_system(
         }
     }
 }
+
+/// Turn `leader` into a pack leader and spawn its followers on free tiles nearby
+#[allow(clippy::too_many_arguments)]
+fn spawn_pack_followers(
+    commands: &mut Commands,
+    map: &CurrentMap,
+    template: &MonsterTemplate,
+    leader: Entity,
+    leader_pos: Position,
+    occupied: &mut HashSet<Position>,
+    number: usize,
+    initial_state: AiState,
+    rng: &mut GameRng,
+) {
+    commands.entity(leader).insert((PackLeader, PackMember { leader }));
+
+    let follower_count = rng.gen_range(PACK_MIN_FOLLOWERS..=PACK_MAX_FOLLOWERS);
+
+    // Free tiles around the leader, nearest first
+    let mut spots: Vec<Position> = (-PACK_SPAWN_RADIUS..=PACK_SPAWN_RADIUS)
+        .flat_map(|dy| (-PACK_SPAWN_RADIUS..=PACK_SPAWN_RADIUS).map(move |dx| (dx, dy)))
+        .map(|(dx, dy)| Position::new(leader_pos.x + dx, leader_pos.y + dy))
+        .filter(|pos| !occupied.contains(pos) && map.is_walkable(pos.x, pos.y) && !map.in_arena(*pos))
+        .collect();
+    spots.sort_by_key(|pos| grid_distance(leader_pos, *pos));
+
+    let spots: Vec<Position> = spots.into_iter().take(follower_count).collect();
+    for (j, pos) in spots.iter().enumerate() {
+        let name = format!("{} #{}{}", template.name, number, (b'a' + j as u8) as char);
+        let follower = spawn_monster(commands, template, *pos, name, initial_state);
+        commands.entity(follower).insert(PackMember { leader });
+        occupied.insert(*pos);
+    }
+
+    info!("{} #{} leads a pack of {}", template.name, number, spots.len());
+}
+
+/// Spawn a single monster from its template
+///
+/// Shared by level generation and summoning spells.
+pub fn spawn_monster(
+    commands: &mut Commands,
+    template: &MonsterTemplate,
+    pos: Position,
+    name: String,
+    initial_state: AiState,
+) -> Entity {
+    let resistances = template
+        .resistances
+        .iter()
+        .fold(Resistances::new(), |acc, (damage_type, resistance)| acc.with(*damage_type, *resistance));
+
+    let mut monster = commands.spawn((
+        Enemy,
+        template.faction,
+        pos,
+        Name::new(name),
+        Health::new(template.health),
+        CombatStats::new(template.power, template.defense)
+            .with_accuracy(template.accuracy)
+            .with_evasion(template.evasion),
+        Weapon::new(template.weapon.0, template.weapon.1),
+        resistances,
+        Renderable::new(template.color).with_tile(template.tile).with_glyph(template.glyph),
+        Viewshed::new(template.fov_radius),
+        // AI
+        (initial_state, AiTarget::default(), GuardPost(pos), template.behaviour),
+    ));
+
+    if !template.spells.is_empty() {
+        monster.insert(Spellbook::new(template.spells));
+    }
+    if !template.boss_phases.is_empty() {
+        monster.insert(Boss::new(template.boss_phases));
+    }
+
+    monster.id()
+}
@@ -0,0 +1,152 @@
+/// Auto-explore - walking towards unexplored space one step per turn
+
+use bevy::prelude::*;
+use std::collections::HashSet;
+use crate::components::{Player, Position, Viewshed, Health, Name, Faction, Grudges, Trap, Concealed};
+use crate::resources::{
+    CurrentMap, VisibilityMap, DijkstraMap, PlayerActionPoints, CombatLog, FactionTable, Allegiance,
+    ActionInput, PlayerAction,
+};
+use crate::systems::movement::PendingMovement;
+use crate::systems::mouse::TravelPlan;
+use crate::constants::MOVEMENT_ACTION_COST;
+
+// ============================================================================
+// RESOURCES
+// ============================================================================
+
+/// Whether the player is auto-exploring, and their health when it last checked
+#[derive(Resource, Default)]
+pub struct AutoExplore {
+    pub active: bool,
+    pub last_health: i32,
+}
+
+impl AutoExplore {
+    pub fn stop(&mut self, combat_log: &mut CombatLog, reason: impl Into<String>) {
+        self.active = false;
+        combat_log.add_message(reason.into());
+    }
+}
+
+pub type PlayerSight<'a> = (Entity, &'a Position, &'a Viewshed, &'a Health, &'a Faction, Option<&'a Grudges>);
+pub type OtherActor<'a> = (Entity, &'a Position, &'a Name, &'a Faction, Option<&'a Grudges>);
+/// Traps the player has found
+pub type KnownTrapFilter = (With<Trap>, Without<Concealed>);
+
+/// Name of a hostile actor the player can currently see, if any
+pub fn visible_hostile<'a>(
+    factions: &FactionTable,
+    player: &PlayerSight,
+    actors: impl Iterator<Item = OtherActor<'a>>,
+) -> Option<&'a Name> {
+    let (entity, _, viewshed, _, faction, grudges) = *player;
+    let me = Allegiance::new(entity, faction, grudges);
+
+    actors
+        .filter(|(_, pos, ..)| viewshed.can_see(pos))
+        .find(|(other, _, _, faction, grudges)| {
+            factions.is_hostile(me, Allegiance::new(*other, faction, *grudges))
+        })
+        .map(|(_, _, name, ..)| name)
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Start auto-exploring (O by default), unless enemies are in sight
+pub fn auto_explore_input_system(
+    actions: Res<ActionInput>,
+    mut auto_explore: ResMut<AutoExplore>,
+    mut combat_log: ResMut<CombatLog>,
+    factions: Res<FactionTable>,
+    player_query: Query<PlayerSight, With<Player>>,
+    actor_query: Query<OtherActor, Without<Player>>,
+) {
+    if !actions.just_pressed(PlayerAction::AutoExplore) || auto_explore.active {
+        return;
+    }
+    let Ok(player) = player_query.get_single() else {
+        return;
+    };
+
+    if let Some(name) = visible_hostile(&factions, &player, actor_query.iter()) {
+        combat_log.add_message(format!("Not with {} in sight!", name.0));
+        return;
+    }
+
+    auto_explore.active = true;
+    auto_explore.last_health = player.3.current;
+    combat_log.add_message("You start exploring.".to_string());
+}
+
+/// Queue the next exploration step as this turn's movement
+///
+/// Exploring stops on any other player action, when a hostile comes into
+/// view, when the player is hurt, or when nothing reachable is left unseen.
+/// It never steps onto a trap the player knows about, stopping instead if one
+/// is in the way.
+#[allow(clippy::too_many_arguments)]
+pub fn auto_explore_step_system(
+    actions: Res<ActionInput>,
+    mut auto_explore: ResMut<AutoExplore>,
+    mut pending_movement: ResMut<PendingMovement>,
+    mut combat_log: ResMut<CombatLog>,
+    action_points: Res<PlayerActionPoints>,
+    travel: Res<TravelPlan>,
+    map: Res<CurrentMap>,
+    visibility_map: Res<VisibilityMap>,
+    factions: Res<FactionTable>,
+    player_query: Query<PlayerSight, With<Player>>,
+    actor_query: Query<OtherActor, Without<Player>>,
+    trap_query: Query<&Position, KnownTrapFilter>,
+) {
+    if !auto_explore.active {
+        return;
+    }
+
+    let interrupted = PlayerAction::ALL
+        .into_iter()
+        .any(|action| action != PlayerAction::AutoExplore && actions.just_pressed(action));
+    if interrupted || travel.is_active() {
+        auto_explore.active = false;
+        return;
+    }
+
+    let Ok(player) = player_query.get_single() else {
+        return;
+    };
+    let (_, player_pos, _, health, ..) = player;
+
+    if let Some(name) = visible_hostile(&factions, &player, actor_query.iter()) {
+        auto_explore.stop(&mut combat_log, format!("You spot {} and stop exploring.", name.0));
+        return;
+    }
+    if health.current < auto_explore.last_health {
+        auto_explore.stop(&mut combat_log, "You are hurt and stop exploring.");
+        return;
+    }
+    auto_explore.last_health = health.current;
+
+    if !action_points.can_afford(MOVEMENT_ACTION_COST) {
+        return;
+    }
+
+    let traps: HashSet<Position> = trap_query.iter().copied().collect();
+    let explore_map = DijkstraMap::explore_map(&map, &visibility_map, |pos| traps.contains(&pos));
+    let Some(next) = explore_map.downhill_step(&map, *player_pos, |_| false) else {
+        let trapped = DijkstraMap::explore_map(&map, &visibility_map, |_| false)
+            .downhill_step(&map, *player_pos, |_| false)
+            .is_some();
+        if trapped {
+            auto_explore.stop(&mut combat_log, "A known trap is in the way; you stop exploring.");
+        } else {
+            auto_explore.stop(&mut combat_log, "There is nothing left here to explore.");
+        }
+        return;
+    };
+
+    pending_movement.dx = next.x - player_pos.x;
+    pending_movement.dy = next.y - player_pos.y;
+}
@@ -0,0 +1,61 @@
+/// Faction systems - neutral actors turning on whoever attacks them
+
+use bevy::prelude::*;
+use crate::components::{Faction, Reaction, Grudges, Name};
+use crate::resources::{CombatLog, FactionTable};
+use crate::systems::combat::{AttackMissed, DamageDealt};
+
+// ============================================================================
+// GRUDGES
+// ============================================================================
+
+/// Make every attacked actor hold a grudge against its attacker
+///
+/// A neutral creature only fights back once it has been attacked.
+pub fn record_grudges_system(
+    mut commands: Commands,
+    mut missed_events: EventReader<AttackMissed>,
+    mut damage_events: EventReader<DamageDealt>,
+    factions: Res<FactionTable>,
+    mut combat_log: ResMut<CombatLog>,
+    mut actor_query: Query<(&Faction, Option<&mut Grudges>, &Name)>,
+) {
+    let attacks = missed_events
+        .read()
+        .map(|event| (event.attacker, event.defender))
+        .chain(damage_events.read().filter_map(|event| event.source.map(|source| (source, event.target))))
+        .collect::<Vec<_>>();
+
+    for (attacker, defender) in attacks {
+        if attacker == defender {
+            continue;
+        }
+        let Ok((attacker_faction, ..)) = actor_query.get(attacker) else {
+            continue;
+        };
+        let attacker_faction = *attacker_faction;
+
+        let Ok((defender_faction, grudges, defender_name)) = actor_query.get_mut(defender) else {
+            continue;
+        };
+
+        // Only worth noting when the attack starts a new fight
+        let already_hostile = factions.reaction(*defender_faction, attacker_faction) == Reaction::Hostile
+            || grudges.as_ref().is_some_and(|grudges| grudges.holds_against(attacker));
+        if already_hostile {
+            continue;
+        }
+
+        combat_log.add_message(format!("{} turns hostile!", defender_name.0));
+        match grudges {
+            Some(mut grudges) => {
+                grudges.against.insert(attacker);
+            }
+            None => {
+                let mut grudges = Grudges::default();
+                grudges.against.insert(attacker);
+                commands.entity(defender).insert(grudges);
+            }
+        }
+    }
+}
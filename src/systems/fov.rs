@@ -2,8 +2,10 @@
 
 use bevy::prelude::*;
 use bracket_pathfinding::prelude::*;
-use crate::components::{Player, Position, Viewshed};
-use crate::resources::{CurrentMap, VisibilityMap, VisibilityState};
+use std::collections::HashMap;
+use crate::components::{Player, Position, Viewshed, Concealed, Name, Renderable, Memorable};
+use crate::resources::{CurrentMap, VisibilityMap, VisibilityState, EntityMemory, RememberedThing, LightMap};
+use crate::resources::grid_distance;
 
 // ============================================================================
 // COMPONENTS
@@ -15,22 +17,31 @@ pub struct MapTile {
     pub position: Position,
 }
 
-/// Stores the original color of a tile for visibility calculations
-#[derive(Component)]
-pub struct TileBaseColor(pub Color);
+/// A color at 50% brightness, for things remembered but not in view
+pub fn dimmed(color: Color) -> Color {
+    let c = color.to_srgba();
+    Color::srgb(c.red * 0.5, c.green * 0.5, c.blue * 0.5)
+}
 
 // ============================================================================
 // SYSTEMS
 // ============================================================================
 
 /// Calculate FOV for entities that have moved
+///
+/// The player also needs light to see: tiles in line of sight only count
+/// when lit (or adjacent), so the player's FOV is recalculated whenever the
+/// light map changes. Monsters see in the dark.
 pub fn calculate_fov_system(
-    mut query: Query<(&Position, &mut Viewshed), Changed<Position>>,
+    mut query: Query<(Ref<Position>, &mut Viewshed, Has<Player>)>,
     map: Res<CurrentMap>,
+    light_map: Res<LightMap>,
 ) {
-    for (pos, mut viewshed) in query.iter_mut() {
-        // Changed<Position> already filters when to recalculate
-        // No need for dirty flag check here
+    for (pos, mut viewshed, is_player) in query.iter_mut() {
+        let needs_update = pos.is_changed() || (is_player && light_map.is_changed());
+        if !needs_update {
+            continue;
+        }
 
         // Convert Position to bracket-lib Point
         let origin = Point::new(pos.x, pos.y);
@@ -46,6 +57,7 @@ pub fn calculate_fov_system(
         viewshed.visible_tiles = visible
             .iter()
             .map(|pt| Position::new(pt.x, pt.y))
+            .filter(|tile| !is_player || light_map.is_lit(*tile) || grid_distance(*tile, *pos) <= 1)
             .collect();
 
         info!("FOV calculated at ({}, {}): {} tiles visible",
@@ -77,32 +89,31 @@ pub fn update_visibility_map_system(
     }
 }
 
-/// Update tile colors based on visibility state
+/// Update tile colors based on visibility state and light
 pub fn apply_tile_visibility_system(
     visibility_map: Res<VisibilityMap>,
-    mut query: Query<(&MapTile, &TileBaseColor, &mut Sprite)>,
+    light_map: Res<LightMap>,
+    mut query: Query<(&MapTile, &Renderable, &mut Sprite)>,
 ) {
-    // Only run when visibility map changes (optimization)
-    if !visibility_map.is_changed() {
-        return;
-    }
+    // Only redo every tile when visibility or light changes (optimization)
+    let all_tiles = visibility_map.is_changed() || light_map.is_changed();
 
-    for (tile, base_color, mut sprite) in query.iter_mut() {
+    for (tile, renderable, mut sprite) in query.iter_mut() {
+        if !all_tiles && !sprite.is_added() {
+            continue;
+        }
+
+        let base_color = renderable.color;
         let visibility = visibility_map.get(&tile.position);
 
         match visibility {
             VisibilityState::Visible => {
-                // Use actual tile colors (brightened in constants.rs)
-                sprite.color = base_color.0;
+                // Tile colors tinted by the light falling on them
+                sprite.color = light_map.shade(base_color, tile.position);
             }
             VisibilityState::Explored => {
                 // Dimmed to 50% for fog of war effect
-                let c = base_color.0.to_srgba();
-                sprite.color = Color::srgb(
-                    c.red * 0.5,
-                    c.green * 0.5,
-                    c.blue * 0.5,
-                );
+                sprite.color = dimmed(base_color);
             }
             VisibilityState::Unseen => {
                 // Completely black
@@ -112,21 +123,73 @@ pub fn apply_tile_visibility_system(
     }
 }
 
+/// Record the memorable entities on every tile the player can see
+///
+/// Visible tiles are overwritten, so things that have gone from view are
+/// forgotten there too.
+#[allow(clippy::type_complexity)]
+pub fn update_entity_memory_system(
+    player_query: Query<&Viewshed, With<Player>>,
+    memorable_query: Query<(Entity, &Position, &Name), (With<Memorable>, Without<Concealed>)>,
+    mut memory: ResMut<EntityMemory>,
+) {
+    let Ok(viewshed) = player_query.get_single() else {
+        return;
+    };
+
+    let mut seen: HashMap<Position, Vec<RememberedThing>> = HashMap::new();
+    for (entity, pos, name) in memorable_query.iter() {
+        if viewshed.can_see(pos) {
+            seen.entry(*pos).or_default().push(RememberedThing {
+                entity,
+                name: name.0.clone(),
+            });
+        }
+    }
+
+    for pos in &viewshed.visible_tiles {
+        let things = seen.remove(pos).unwrap_or_default();
+        if !things.is_empty() || !memory.at(*pos).is_empty() {
+            memory.remember(*pos, things);
+        }
+    }
+}
+
 /// Hide entities (enemies, items) outside player's FOV
+///
+/// Runs every frame so monsters that walk into (or out of) view are updated
+/// even when the player stands still. Memorable entities stay shown, dimmed,
+/// while the player remembers them where they are. Concealed entities
+/// (undetected traps) stay hidden even when in view.
+#[allow(clippy::type_complexity)]
 pub fn hide_entities_outside_fov_system(
-    player_query: Query<&Viewshed, (With<Player>, Changed<Viewshed>)>,
+    player_query: Query<&Viewshed, With<Player>>,
+    memory: Res<EntityMemory>,
     mut entity_query: Query<
-        (&Position, &mut Visibility),
-        (Without<Player>, Without<MapTile>)
+        (Entity, &Position, &mut Visibility, Option<&mut Sprite>, Option<&Renderable>, Has<Memorable>),
+        (Without<Player>, Without<MapTile>, Without<Concealed>)
     >,
 ) {
-    // Only process if player viewshed changed
     if let Ok(viewshed) = player_query.get_single() {
-        for (pos, mut visibility) in entity_query.iter_mut() {
-            if viewshed.can_see(pos) {
-                *visibility = Visibility::Visible;
+        for (entity, pos, mut visibility, sprite, renderable, memorable) in entity_query.iter_mut() {
+            let in_view = viewshed.can_see(pos);
+            let remembered = memorable && !in_view && memory.remembers(entity, *pos);
+            let target = if in_view || remembered {
+                Visibility::Visible
             } else {
-                *visibility = Visibility::Hidden;
+                Visibility::Hidden
+            };
+
+            if let (Some(mut sprite), Some(renderable), true) = (sprite, renderable, memorable) {
+                let color = if remembered { dimmed(renderable.color) } else { renderable.color };
+                if sprite.color != color {
+                    sprite.color = color;
+                }
+            }
+
+            // Avoid triggering change detection when nothing changed
+            if *visibility != target {
+                *visibility = target;
             }
         }
     }
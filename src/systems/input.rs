@@ -0,0 +1,104 @@
+/// Input systems - loading keybindings, turning key presses into actions,
+/// and replaying or recording those actions
+
+use bevy::prelude::*;
+use crate::resources::{KeyBindings, ActionInput, CombatLog, ActionSource, ActionReplay, ActionRecording, GameRng};
+use crate::constants::KEYBINDINGS_FILE;
+
+/// Replace the default bindings with the user's bindings file, if there is one
+///
+/// Problems in the file (unknown names, conflicting keys) are logged and the
+/// offending entries skipped; the rest of the file still applies.
+pub fn load_keybindings_system(
+    mut bindings: ResMut<KeyBindings>,
+    mut combat_log: ResMut<CombatLog>,
+) {
+    let text = match std::fs::read_to_string(KEYBINDINGS_FILE) {
+        Ok(text) => text,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            info!("No {} found, using default keybindings", KEYBINDINGS_FILE);
+            return;
+        }
+        Err(err) => {
+            warn!("Could not read {}: {}", KEYBINDINGS_FILE, err);
+            return;
+        }
+    };
+
+    let (loaded, problems) = KeyBindings::parse(&text);
+    for problem in &problems {
+        warn!("{}: {}", KEYBINDINGS_FILE, problem);
+    }
+    if !problems.is_empty() {
+        combat_log.add_message(format!(
+            "{} has {} problem(s); some keys may not work as expected.",
+            KEYBINDINGS_FILE,
+            problems.len()
+        ));
+    }
+
+    *bindings = loaded;
+    info!("Loaded keybindings from {}", KEYBINDINGS_FILE);
+}
+
+/// Translate this frame's key presses into player actions
+pub fn keyboard_actions_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut actions: ResMut<ActionInput>,
+) {
+    for key in keyboard.get_just_pressed() {
+        if let Some(action) = bindings.action_for(*key) {
+            actions.press(action);
+        }
+    }
+}
+
+/// Forget this frame's actions once the game rules have seen them
+pub fn clear_action_input_system(mut actions: ResMut<ActionInput>) {
+    actions.clear();
+}
+
+/// Give the player this frame's actions from the replay
+///
+/// When the replay runs out the keyboard takes over.
+pub fn replay_actions_system(
+    mut replay: ResMut<ActionReplay>,
+    mut source: ResMut<ActionSource>,
+    mut actions: ResMut<ActionInput>,
+    mut combat_log: ResMut<CombatLog>,
+) {
+    let Some(frame) = replay.next_frame() else {
+        *source = ActionSource::Keyboard;
+        combat_log.add_message("The replay is over; you have control.".to_string());
+        return;
+    };
+
+    for action in frame {
+        actions.press(action);
+    }
+}
+
+/// Add this frame's actions to the recording
+pub fn record_actions_system(
+    actions: Res<ActionInput>,
+    mut recording: ResMut<ActionRecording>,
+) {
+    recording.record(actions.pressed());
+}
+
+/// Write the recording out when the game exits
+pub fn save_recording_system(
+    mut exit_events: EventReader<AppExit>,
+    recording: Res<ActionRecording>,
+    rng: Res<GameRng>,
+) {
+    if exit_events.read().next().is_none() {
+        return;
+    }
+
+    match std::fs::write(&recording.path, recording.to_text(rng.seed())) {
+        Ok(()) => info!("Saved the player's actions to {}", recording.path),
+        Err(err) => warn!("Could not save {}: {}", recording.path, err),
+    }
+}
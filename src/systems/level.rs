@@ -0,0 +1,173 @@
+/// Level systems - building map tiles and taking the stairs down
+
+use bevy::prelude::*;
+use std::collections::HashSet;
+use crate::components::{Player, Position, Enemy, Trap, Name, Companion, LightSource, Renderable};
+use crate::resources::{
+    CurrentMap, TileType, VisibilityMap, PlayerActionPoints, CombatLog, LogEntryKind, Bestiary, Depth,
+    ActionInput, PlayerAction, EntityMemory, GameRng, grid_distance,
+};
+use crate::resources::dijkstra::NEIGHBOURS;
+use crate::systems::fov::MapTile;
+use crate::systems::enemy_spawning::spawn_enemies_system;
+use crate::systems::traps::spawn_traps_system;
+use crate::systems::boss::spawn_boss;
+use crate::systems::lighting::spawn_light_sources_system;
+use crate::constants::*;
+
+// ============================================================================
+// RESOURCES
+// ============================================================================
+
+/// Set when the player asks to take the stairs this frame
+#[derive(Resource, Default)]
+pub struct PendingDescend(pub bool);
+
+// ============================================================================
+// MAP TILES
+// ============================================================================
+
+/// Spawn an entity for every tile of the map
+pub fn spawn_map_tiles(commands: &mut Commands, map: &CurrentMap) {
+    for y in 0..map.height {
+        for x in 0..map.width {
+            let tile_type = map.tiles[y][x];
+            let (base_color, background) = match tile_type {
+                TileType::Floor => (COLOR_FLOOR, Color::NONE),
+                TileType::Wall => (COLOR_WALL, COLOR_WALL_BACKGROUND),
+                TileType::DownStairs => (COLOR_DOWN_STAIRS, Color::NONE),
+            };
+            let mut renderable = Renderable::new(base_color)
+                .with_glyph(tile_type.glyph())
+                .with_background(background)
+                .with_layer(Z_LAYER_FLOOR);
+            renderable.tile = map.tile_index(x as i32, y as i32);
+
+            commands.spawn((
+                MapTile {
+                    position: Position::new(x as i32, y as i32),
+                },
+                renderable,
+            ));
+        }
+    }
+}
+
+// ============================================================================
+// STAIRS
+// ============================================================================
+
+/// Capture the descend action (> by default) to take the down stairs the player is standing on
+pub fn player_descend_input_system(
+    actions: Res<ActionInput>,
+    mut pending_descend: ResMut<PendingDescend>,
+    action_points: Res<PlayerActionPoints>,
+    map: Res<CurrentMap>,
+    player_query: Query<&Position, With<Player>>,
+    mut combat_log: ResMut<CombatLog>,
+) {
+    pending_descend.0 = false;
+
+    if !actions.just_pressed(PlayerAction::Descend) {
+        return;
+    }
+
+    if !action_points.can_afford(MOVEMENT_ACTION_COST) {
+        return;
+    }
+
+    let Ok(player_pos) = player_query.get_single() else {
+        return;
+    };
+
+    if map.get_tile(player_pos.x, player_pos.y) == Some(TileType::DownStairs) {
+        pending_descend.0 = true;
+    } else {
+        combat_log.add_message("There are no stairs down here.".to_string());
+    }
+}
+
+/// Replace the current level with the next depth
+///
+/// Companions close to the player follow them down; everything else on the
+/// old level (tiles, monsters, traps, lights, stragglers) is removed.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn descend_stairs_system(
+    mut commands: Commands,
+    mut pending_descend: ResMut<PendingDescend>,
+    mut action_points: ResMut<PlayerActionPoints>,
+    mut depth: ResMut<Depth>,
+    mut visibility_map: ResMut<VisibilityMap>,
+    mut memory: ResMut<EntityMemory>,
+    mut combat_log: ResMut<CombatLog>,
+    bestiary: Res<Bestiary>,
+    mut rng: ResMut<GameRng>,
+    mut player_query: Query<&mut Position, (With<Player>, Without<Companion>)>,
+    mut companion_query: Query<(Entity, &mut Position, &Name), (With<Companion>, Without<Player>)>,
+    level_query: Query<Entity, Or<(With<MapTile>, With<Enemy>, With<Trap>, (With<LightSource>, Without<Player>))>>,
+) {
+    if !std::mem::take(&mut pending_descend.0) {
+        return;
+    }
+
+    let Ok(mut player_pos) = player_query.get_single_mut() else {
+        return;
+    };
+
+    // Everything belonging to the old level goes
+    for entity in level_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    depth.0 += 1;
+    let map = CurrentMap::for_depth(depth.0, &mut rng);
+    spawn_map_tiles(&mut commands, &map);
+
+    // Arrive somewhere other than on the next stairs or inside the arena
+    let arrival = std::iter::repeat_with(|| map.random_walkable_position(&mut rng))
+        .take(100)
+        .flatten()
+        .find(|pos| map.get_tile(pos.x, pos.y) == Some(TileType::Floor) && !map.in_arena(*pos))
+        .unwrap_or(*player_pos);
+    let departure = *player_pos;
+    *player_pos = arrival;
+
+    // Nearby companions come along, the rest are left behind
+    let mut occupied = HashSet::from([arrival]);
+    let mut free_tiles = NEIGHBOURS
+        .iter()
+        .map(|(dx, dy)| Position::new(arrival.x + dx, arrival.y + dy))
+        .filter(|pos| map.is_walkable(pos.x, pos.y));
+    for (companion, mut companion_pos, name) in companion_query.iter_mut() {
+        let close_enough = grid_distance(*companion_pos, departure) <= COMPANION_STAIRS_RANGE;
+        let tile = if close_enough { free_tiles.next() } else { None };
+        match tile {
+            Some(tile) => {
+                *companion_pos = tile;
+                occupied.insert(tile);
+                combat_log.add_message(format!("{} follows you down the stairs.", name.0));
+            }
+            None => {
+                commands.entity(companion).despawn_recursive();
+                combat_log.add_message(format!("{} is left behind.", name.0));
+            }
+        }
+    }
+
+    spawn_enemies_system(commands.reborrow(), &map, &bestiary, &occupied, &mut rng);
+    spawn_traps_system(commands.reborrow(), &map, &occupied, &mut rng);
+    spawn_light_sources_system(commands.reborrow(), &map, arrival, &mut rng);
+    spawn_boss(&mut commands, &map, &bestiary);
+
+    let map_has_arena = map.arena.is_some();
+    commands.insert_resource(map);
+    visibility_map.clear();
+    memory.clear();
+    action_points.spend(MOVEMENT_ACTION_COST);
+
+    combat_log.add_message(format!("You descend to depth {}.", depth.0));
+    if map_has_arena {
+        combat_log.add_entry(LogEntryKind::Kill, "An icy presence waits behind the arena door...".to_string());
+    }
+    info!("Descended to depth {}", depth.0);
+}
@@ -0,0 +1,98 @@
+/// Lighting systems - placing light sources and building the light map
+
+use bevy::prelude::*;
+use bracket_pathfinding::prelude::*;
+use crate::components::{Position, Name, Renderable, Memorable, LightSource};
+use crate::resources::{CurrentMap, TileType, LightMap, Depth, GameRng};
+use crate::resources::dijkstra::NEIGHBOURS;
+use crate::constants::*;
+
+// ============================================================================
+// LIGHT PLACEMENT
+// ============================================================================
+
+/// Place wall torches and patches of glowing fungi around the level
+///
+/// Torches go on floor tiles against a wall; fungi anywhere on the floor.
+/// Neither is placed on the player's tile, the stairs or in the boss arena.
+pub fn spawn_light_sources_system(
+    mut commands: Commands,
+    map: &CurrentMap,
+    player_pos: Position,
+    rng: &mut GameRng,
+) {
+    let free_floor = |pos: Position| {
+        map.get_tile(pos.x, pos.y) == Some(TileType::Floor) && pos != player_pos && !map.in_arena(pos)
+    };
+    let against_wall = |pos: Position| {
+        NEIGHBOURS
+            .iter()
+            .any(|(dx, dy)| map.get_tile(pos.x + dx, pos.y + dy) == Some(TileType::Wall))
+    };
+
+    let lights = [
+        ("Torch", TILE_TORCH, '☼', TORCH_COUNT, LightSource::new(TORCH_LIGHT_RADIUS, COLOR_TORCH_LIGHT, 1.0), true),
+        ("Glowing Fungus", TILE_FUNGUS, '"', FUNGUS_COUNT, LightSource::new(FUNGUS_LIGHT_RADIUS, COLOR_FUNGUS_LIGHT, 0.6), false),
+    ];
+
+    for (name, tile, glyph, count, light, needs_wall) in lights {
+        let spots = std::iter::repeat_with(|| map.random_walkable_position(rng))
+            .take(count * 50)
+            .flatten()
+            .filter(|pos| free_floor(*pos) && (!needs_wall || against_wall(*pos)))
+            .take(count);
+
+        for pos in spots {
+            commands.spawn((
+                light,
+                pos,
+                Name::new(name),
+                Renderable::new(light.color)
+                    .with_tile(tile)
+                    .with_glyph(glyph)
+                    .with_size(0.3)
+                    .with_layer(Z_LAYER_ITEMS),
+                Memorable,
+            ));
+            info!("Placed {} at ({}, {})", name, pos.x, pos.y);
+        }
+    }
+}
+
+// ============================================================================
+// LIGHT MAP
+// ============================================================================
+
+/// Rebuild the light map when the map changes or a light appears, moves or goes out
+///
+/// Each light reaches the tiles it has line of sight to within its radius,
+/// fading linearly with distance.
+#[allow(clippy::type_complexity)]
+pub fn update_light_map_system(
+    map: Res<CurrentMap>,
+    depth: Res<Depth>,
+    light_query: Query<(&Position, &LightSource)>,
+    changed_query: Query<(), (With<LightSource>, Or<(Changed<Position>, Added<LightSource>)>)>,
+    mut removed_lights: RemovedComponents<LightSource>,
+    mut light_map: ResMut<LightMap>,
+) {
+    let lights_removed = removed_lights.read().count() > 0;
+    if !map.is_changed() && changed_query.is_empty() && !lights_removed {
+        return;
+    }
+
+    let mut rebuilt = LightMap::new(map.width, map.height, LightMap::ambient_for_depth(depth.0));
+
+    for (pos, light) in light_query.iter() {
+        let origin = Point::new(pos.x, pos.y);
+        for pt in field_of_view_set(origin, light.radius, &*map) {
+            let distance = DistanceAlg::Pythagoras.distance2d(origin, pt);
+            let falloff = 1.0 - distance / (light.radius as f32 + 1.0);
+            if falloff > 0.0 {
+                rebuilt.add_light(Position::new(pt.x, pt.y), light.intensity * falloff, light.color);
+            }
+        }
+    }
+
+    *light_map = rebuilt;
+}
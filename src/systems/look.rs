@@ -0,0 +1,156 @@
+/// Look mode - moving a cursor over the map to examine tiles
+
+use bevy::prelude::*;
+use crate::components::{Player, Position, Concealed};
+use crate::resources::{CurrentMap, VisibilityMap, EntityMemory, ActionInput, PlayerAction, FactionTable};
+use crate::systems::mouse::TravelPlan;
+use crate::systems::ui::{describe_tile, describe_visible_entities, Onlooker, Described};
+use crate::constants::{
+    TILE_SIZE, Z_LAYER_UI, COLOR_LOOK_CURSOR, COLOR_UI_BACKGROUND, COLOR_UI_TEXT, COMBAT_LOG_FONT_SIZE,
+};
+
+// ============================================================================
+// RESOURCES AND COMPONENTS
+// ============================================================================
+
+/// Look mode state: the examined tile while looking, None otherwise
+#[derive(Resource, Default)]
+pub struct LookMode {
+    pub cursor: Option<Position>,
+}
+
+impl LookMode {
+    pub fn is_active(&self) -> bool {
+        self.cursor.is_some()
+    }
+}
+
+/// Marker for the highlight drawn over the examined tile
+#[derive(Component)]
+pub struct LookCursor;
+
+/// Marker for the panel describing the examined tile
+#[derive(Component)]
+pub struct LookPanel;
+
+// ============================================================================
+// SETUP
+// ============================================================================
+
+/// Spawn the (initially hidden) look cursor and description panel
+pub fn spawn_look_ui(mut commands: Commands) {
+    commands.spawn((
+        LookCursor,
+        Sprite {
+            color: COLOR_LOOK_CURSOR,
+            custom_size: Some(Vec2::new(TILE_SIZE, TILE_SIZE)),
+            ..default()
+        },
+        Transform::from_xyz(0.0, 0.0, Z_LAYER_UI),
+        Visibility::Hidden,
+    ));
+
+    commands.spawn((
+        LookPanel,
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(8.0),
+            top: Val::Px(8.0),
+            max_width: Val::Px(360.0),
+            padding: UiRect::all(Val::Px(6.0)),
+            ..default()
+        },
+        BackgroundColor(COLOR_UI_BACKGROUND),
+        Text::new(""),
+        TextFont {
+            font_size: COMBAT_LOG_FONT_SIZE,
+            ..default()
+        },
+        TextColor(COLOR_UI_TEXT),
+        Visibility::Hidden,
+    ));
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Toggle look mode (; by default) and move the cursor with the movement keys
+///
+/// While looking, this swallows every other action so the player doesn't
+/// act. The look toggle itself is left in place, which interrupts resting
+/// and auto-explore like any other action; click-to-travel is cancelled here.
+pub fn look_input_system(
+    mut actions: ResMut<ActionInput>,
+    mut look_mode: ResMut<LookMode>,
+    mut travel: ResMut<TravelPlan>,
+    map: Res<CurrentMap>,
+    player_query: Query<&Position, With<Player>>,
+) {
+    if actions.just_pressed(PlayerAction::Look) {
+        look_mode.cursor = match look_mode.cursor {
+            Some(_) => None,
+            None => {
+                travel.cancel();
+                player_query.get_single().ok().copied()
+            }
+        };
+        return;
+    }
+
+    let Some(cursor) = look_mode.cursor else {
+        return;
+    };
+
+    let (dx, dy) = actions.movement();
+    let moved = Position::new(cursor.x + dx, cursor.y + dy);
+    if map.get_tile(moved.x, moved.y).is_some() {
+        look_mode.cursor = Some(moved);
+    }
+    actions.clear();
+}
+
+/// Place the cursor and describe the examined tile
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn update_look_display_system(
+    look_mode: Res<LookMode>,
+    map: Option<Res<CurrentMap>>,
+    visibility_map: Res<VisibilityMap>,
+    memory: Res<EntityMemory>,
+    factions: Res<FactionTable>,
+    player_query: Query<Onlooker, With<Player>>,
+    entity_query: Query<Described, Without<Concealed>>,
+    mut cursor_query: Query<(&mut Transform, &mut Visibility), (With<LookCursor>, Without<LookPanel>)>,
+    mut panel_query: Query<(&mut Text, &mut Visibility), (With<LookPanel>, Without<LookCursor>)>,
+) {
+    let (Ok((mut transform, mut cursor_visibility)), Ok((mut text, mut panel_visibility))) =
+        (cursor_query.get_single_mut(), panel_query.get_single_mut())
+    else {
+        return;
+    };
+
+    let (Some(cursor), Some(map), Ok(player)) = (look_mode.cursor, map, player_query.get_single()) else {
+        if *cursor_visibility != Visibility::Hidden {
+            *cursor_visibility = Visibility::Hidden;
+            *panel_visibility = Visibility::Hidden;
+        }
+        return;
+    };
+
+    let visible_things = describe_visible_entities(cursor, player, &factions, &entity_query);
+    let lines = describe_tile(&map, &visibility_map, &memory, cursor, &visible_things)
+        .unwrap_or_else(|| vec!["Unexplored".to_string()]);
+    let description = format!("Looking (; to stop)\n{}", lines.join("\n"));
+
+    if text.0 != description {
+        text.0 = description;
+    }
+    let target = Vec3::new(cursor.x as f32 * TILE_SIZE, cursor.y as f32 * TILE_SIZE, Z_LAYER_UI);
+    if transform.translation != target {
+        transform.translation = target;
+    }
+    if *cursor_visibility != Visibility::Visible {
+        *cursor_visibility = Visibility::Visible;
+        *panel_visibility = Visibility::Visible;
+    }
+}
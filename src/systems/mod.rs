@@ -8,21 +8,148 @@ pub mod turn_manager;
 pub mod enemy_ai;
 pub mod combat;
 pub mod enemy_spawning;
+pub mod traps;
+pub mod ui;
+pub mod pathfinding;
+pub mod noise;
+pub mod spells;
+pub mod factions;
+pub mod companion;
+pub mod level;
+pub mod boss;
+pub mod mouse;
+pub mod input;
+pub mod explore;
+pub mod rest;
+pub mod look;
+pub mod lighting;
+pub mod tileset;
+pub mod sprites;
+pub mod bot;
 
-pub use movement::{player_input_system, apply_movement_system, camera_follow_system};
+pub use movement::{EntityMoved, player_input_system, apply_movement_system, camera_follow_system};
 pub use fov::{
-    MapTile, TileBaseColor,
+    MapTile,
     calculate_fov_system,
     update_visibility_map_system,
     apply_tile_visibility_system,
+    update_entity_memory_system,
     hide_entities_outside_fov_system,
+    dimmed,
 };
 pub use turn_manager::{check_turn_end_system, start_player_turn_system, enemy_turn_system};
-pub use enemy_ai::enemy_action_system;
+pub use enemy_ai::{
+    AiDebugOverlay, AiStateLabel,
+    update_ai_state_system,
+    break_leaderless_packs_system,
+    enemy_action_system,
+    toggle_ai_debug_overlay_system,
+    update_ai_debug_overlay_system,
+};
 pub use combat::{
     PendingAttack,
+    CombatSequence,
+    AttackKind,
+    AttackIntent,
+    AttackMissed,
+    DamageDealt,
+    EntityKilled,
+    HealApplied,
+    AttackOutcome,
+    HitDistribution,
+    hit_distribution,
+    damage_range,
     player_attack_input_system,
     execute_attack_system,
+    resolve_attack_system,
+    record_combat_events_system,
+    remove_dead_entities_system,
     check_player_death_system,
 };
-pub use enemy_spawning::spawn_enemies_system;
+pub use enemy_spawning::{spawn_enemies_system, spawn_monster};
+pub use traps::{
+    PendingDisarm,
+    spawn_traps_system,
+    detect_traps_system,
+    trigger_traps_system,
+    player_disarm_input_system,
+    execute_disarm_system,
+};
+pub use ui::{
+    CombatLogPanel, CombatLogLine, CombatLogHeader, HoverTooltip,
+    spawn_combat_log_ui,
+    spawn_hover_tooltip_ui,
+    combat_log_scroll_input_system,
+    update_combat_log_ui_system,
+    update_hover_tooltip_system,
+    describe_tile,
+    wound_description,
+    describe_entity,
+    describe_visible_entities,
+};
+pub use pathfinding::update_dijkstra_maps_system;
+pub use noise::{
+    NoiseEvent,
+    emit_action_noise_system,
+    hear_noise_system,
+    toggle_sneak_system,
+};
+pub use spells::{CastSpell, resolve_spell_system};
+pub use factions::record_grudges_system;
+pub use companion::{
+    spawn_starting_pet,
+    companion_command_input_system,
+    companion_target_system,
+    companion_action_system,
+};
+pub use level::{
+    PendingDescend,
+    spawn_map_tiles,
+    player_descend_input_system,
+    descend_stairs_system,
+};
+pub use boss::{spawn_boss, boss_phase_system, check_boss_victory_system};
+pub use mouse::{
+    HoveredTile,
+    TravelPlan,
+    world_to_grid,
+    update_hovered_tile_system,
+    click_to_travel_system,
+    interrupt_travel_system,
+    travel_step_system,
+};
+pub use input::{
+    load_keybindings_system,
+    keyboard_actions_system,
+    clear_action_input_system,
+    replay_actions_system,
+    record_actions_system,
+    save_recording_system,
+};
+pub use explore::{AutoExplore, auto_explore_input_system, auto_explore_step_system};
+pub use rest::{
+    Resting,
+    player_wait_input_system,
+    rest_input_system,
+    rest_turn_system,
+    natural_regeneration_system,
+};
+pub use look::{
+    LookMode, LookCursor, LookPanel,
+    spawn_look_ui,
+    look_input_system,
+    update_look_display_system,
+};
+pub use lighting::{spawn_light_sources_system, update_light_map_system};
+pub use tileset::{
+    GlyphBackground,
+    GlyphText,
+    load_tileset_system,
+    toggle_render_mode_system,
+    apply_tileset_system,
+    spawn_glyph_background,
+    update_glyph_backgrounds_system,
+    update_glyph_text_system,
+};
+pub use sprites::{grid_to_world, attach_sprites_system, update_sprite_positions_system};
+pub use bot::bot_actions_system;
@@ -0,0 +1,191 @@
+/// Mouse input - hovering over tiles and click-to-travel
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use std::collections::{HashSet, VecDeque};
+use crate::components::{Player, Position, Viewshed, Enemy, Name};
+use crate::resources::{
+    CurrentMap, VisibilityMap, VisibilityState, DijkstraMap, PlayerActionPoints, CombatLog,
+    ActionInput, PlayerAction,
+};
+use crate::systems::explore::KnownTrapFilter;
+use crate::constants::{TILE_SIZE, MOVEMENT_ACTION_COST};
+
+// ============================================================================
+// RESOURCES
+// ============================================================================
+
+/// Grid tile currently under the mouse cursor (None when off the map)
+#[derive(Resource, Default)]
+pub struct HoveredTile(pub Option<Position>);
+
+/// Route the player is walking after clicking a tile
+#[derive(Resource, Default)]
+pub struct TravelPlan {
+    pub path: VecDeque<Position>,
+    /// Monsters already in view when travel started (they don't interrupt)
+    pub seen_monsters: HashSet<Entity>,
+}
+
+impl TravelPlan {
+    pub fn is_active(&self) -> bool {
+        !self.path.is_empty()
+    }
+
+    pub fn cancel(&mut self) {
+        self.path.clear();
+        self.seen_monsters.clear();
+    }
+}
+
+/// Grid position of a world-space point (tiles are centred on multiples of TILE_SIZE)
+pub fn world_to_grid(world: Vec2) -> Position {
+    Position::new(
+        (world.x / TILE_SIZE).round() as i32,
+        (world.y / TILE_SIZE).round() as i32,
+    )
+}
+
+// ============================================================================
+// HOVER
+// ============================================================================
+
+/// Convert the cursor position to a grid position through the 2D camera
+pub fn update_hovered_tile_system(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    map: Option<Res<CurrentMap>>,
+    mut hovered: ResMut<HoveredTile>,
+) {
+    let tile = (|| {
+        let cursor = window_query.get_single().ok()?.cursor_position()?;
+        let (camera, camera_transform) = camera_query.get_single().ok()?;
+        let world = camera.viewport_to_world_2d(camera_transform, cursor).ok()?;
+        let pos = world_to_grid(world);
+        map?.get_tile(pos.x, pos.y).map(|_| pos)
+    })();
+
+    if hovered.0 != tile {
+        hovered.0 = tile;
+    }
+}
+
+// ============================================================================
+// CLICK-TO-TRAVEL
+// ============================================================================
+
+/// Left-click an explored tile to start walking there (around known traps)
+#[allow(clippy::too_many_arguments)]
+pub fn click_to_travel_system(
+    mouse: Res<ButtonInput<MouseButton>>,
+    hovered: Res<HoveredTile>,
+    map: Res<CurrentMap>,
+    visibility_map: Res<VisibilityMap>,
+    player_query: Query<(&Position, &Viewshed), With<Player>>,
+    monster_query: Query<(Entity, &Position), With<Enemy>>,
+    trap_query: Query<&Position, KnownTrapFilter>,
+    mut travel: ResMut<TravelPlan>,
+    mut combat_log: ResMut<CombatLog>,
+) {
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let (Some(destination), Ok((player_pos, viewshed))) = (hovered.0, player_query.get_single()) else {
+        return;
+    };
+
+    travel.cancel();
+
+    if visibility_map.get(&destination) == VisibilityState::Unseen || !map.is_walkable(destination.x, destination.y) {
+        combat_log.add_message("You don't know a way there.".to_string());
+        return;
+    }
+
+    // Route over explored tiles only, never across a known trap
+    let traps: HashSet<Position> = trap_query.iter().copied().collect();
+    let known = |pos: Position| visibility_map.get(&pos) != VisibilityState::Unseen && !traps.contains(&pos);
+    let Some(path) = DijkstraMap::build_with(&map, &[destination], known).path_to_goal(&map, *player_pos) else {
+        combat_log.add_message("You don't know a way there.".to_string());
+        return;
+    };
+
+    travel.path = path.into();
+    travel.seen_monsters = monster_query
+        .iter()
+        .filter(|(_, pos)| viewshed.can_see(pos))
+        .map(|(entity, _)| entity)
+        .collect();
+}
+
+/// Stop travelling when a new monster comes into view
+pub fn interrupt_travel_system(
+    mut travel: ResMut<TravelPlan>,
+    mut combat_log: ResMut<CombatLog>,
+    player_query: Query<&Viewshed, With<Player>>,
+    monster_query: Query<(Entity, &Position, &Name), With<Enemy>>,
+) {
+    if !travel.is_active() {
+        return;
+    }
+    let Ok(viewshed) = player_query.get_single() else {
+        return;
+    };
+
+    let newcomer = monster_query
+        .iter()
+        .find(|(entity, pos, _)| viewshed.can_see(pos) && !travel.seen_monsters.contains(entity));
+
+    if let Some((_, _, name)) = newcomer {
+        combat_log.add_message(format!("You spot {} and stop.", name.0));
+        travel.cancel();
+    }
+}
+
+/// Press the move action for the next travel step
+///
+/// Runs with the other action sources, after the frontend's, so travel
+/// steps are recorded like any other move. Any other movement this frame
+/// cancels travel instead, and so does a trap spotted on the route (unless
+/// it is the destination).
+pub fn travel_step_system(
+    mut travel: ResMut<TravelPlan>,
+    mut actions: ResMut<ActionInput>,
+    mut combat_log: ResMut<CombatLog>,
+    action_points: Res<PlayerActionPoints>,
+    player_query: Query<&Position, With<Player>>,
+    trap_query: Query<(&Position, &Name), KnownTrapFilter>,
+) {
+    if !travel.is_active() {
+        return;
+    }
+    if actions.movement() != (0, 0) {
+        travel.cancel();
+        return;
+    }
+    if !action_points.can_afford(MOVEMENT_ACTION_COST) {
+        return;
+    }
+    let Ok(player_pos) = player_query.get_single() else {
+        return;
+    };
+
+    let Some(&next) = travel.path.front() else {
+        return;
+    };
+    if travel.path.len() > 1 {
+        if let Some((_, name)) = trap_query.iter().find(|(pos, _)| **pos == next) {
+            combat_log.add_message(format!("You stop short of the {}.", name.0));
+            travel.cancel();
+            return;
+        }
+    }
+    travel.path.pop_front();
+
+    // Knocked off the route (teleport trap, swapped places...)
+    let Some(step) = PlayerAction::for_direction(next.x - player_pos.x, next.y - player_pos.y) else {
+        travel.cancel();
+        return;
+    };
+
+    actions.press(step);
+}
@@ -1,9 +1,9 @@
 /// Player movement and camera systems
 
 use bevy::prelude::*;
-use crate::components::{Player, Position};
-use crate::resources::{CurrentMap, PlayerActionPoints};
-use crate::constants::{CAMERA_FOLLOW_SPEED, MOVEMENT_ACTION_COST};
+use crate::components::{Player, Position, Sneaking, Companion};
+use crate::resources::{CurrentMap, PlayerActionPoints, ActionInput};
+use crate::constants::{CAMERA_FOLLOW_SPEED, MOVEMENT_ACTION_COST, SNEAK_MOVEMENT_COST};
 
 /// Stores pending movement for the player
 #[derive(Resource, Default)]
@@ -12,35 +12,41 @@ pub struct PendingMovement {
     pub dy: i32,
 }
 
-/// System to handle player input (WASD and arrow keys)
+/// Sent whenever an actor steps onto a new tile
+///
+/// Anything that reacts to being walked on (traps, items) listens for this
+/// instead of polling positions.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct EntityMoved {
+    pub entity: Entity,
+    pub from: Position,
+    pub to: Position,
+}
+
+/// System to handle player movement input (any of the 8 move actions)
+///
+/// Pressing two cardinal moves in the same frame still combines into a diagonal.
 pub fn player_input_system(
-    keyboard: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionInput>,
     mut pending_movement: ResMut<PendingMovement>,
 ) {
-    pending_movement.dx = 0;
-    pending_movement.dy = 0;
-
-    // WASD movement
-    if keyboard.just_pressed(KeyCode::KeyW) || keyboard.just_pressed(KeyCode::ArrowUp) {
-        pending_movement.dy = 1;
-    }
-    if keyboard.just_pressed(KeyCode::KeyS) || keyboard.just_pressed(KeyCode::ArrowDown) {
-        pending_movement.dy = -1;
-    }
-    if keyboard.just_pressed(KeyCode::KeyA) || keyboard.just_pressed(KeyCode::ArrowLeft) {
-        pending_movement.dx = -1;
-    }
-    if keyboard.just_pressed(KeyCode::KeyD) || keyboard.just_pressed(KeyCode::ArrowRight) {
-        pending_movement.dx = 1;
-    }
+    let (dx, dy) = actions.movement();
+    pending_movement.dx = dx;
+    pending_movement.dy = dy;
 }
 
 /// System to apply movement with collision detection and action point consumption
+///
+/// Sneaking moves cost SNEAK_MOVEMENT_COST; the overspend is paid back by
+/// skipping part of the following turn. Walking into a companion swaps places.
+#[allow(clippy::type_complexity)]
 pub fn apply_movement_system(
-    mut query: Query<&mut Position, With<Player>>,
+    mut query: Query<(Entity, &mut Position, Option<&Sneaking>), With<Player>>,
+    mut companion_query: Query<(Entity, &mut Position), (With<Companion>, Without<Player>)>,
     pending_movement: Res<PendingMovement>,
     map: Res<CurrentMap>,
     mut action_points: ResMut<PlayerActionPoints>,
+    mut moved_events: EventWriter<EntityMoved>,
 ) {
     // Only move if there's pending movement
     if pending_movement.dx == 0 && pending_movement.dy == 0 {
@@ -54,17 +60,27 @@ pub fn apply_movement_system(
         return;
     }
 
-    for mut pos in query.iter_mut() {
+    for (entity, mut pos, sneaking) in query.iter_mut() {
         let new_x = pos.x + pending_movement.dx;
         let new_y = pos.y + pending_movement.dy;
 
         // Check if the new position is walkable
         if map.is_walkable(new_x, new_y) {
+            let from = *pos;
             pos.x = new_x;
             pos.y = new_y;
+            moved_events.send(EntityMoved { entity, from, to: *pos });
+
+            if let Some((companion, mut companion_pos)) =
+                companion_query.iter_mut().find(|(_, companion_pos)| **companion_pos == *pos)
+            {
+                *companion_pos = from;
+                moved_events.send(EntityMoved { entity: companion, from: *pos, to: from });
+            }
 
             // Spend action points for successful movement
-            action_points.spend(MOVEMENT_ACTION_COST);
+            let cost = if sneaking.is_some() { SNEAK_MOVEMENT_COST } else { MOVEMENT_ACTION_COST };
+            action_points.spend(cost);
 
             info!("Player moved to ({}, {}) - Action points: {}/{}",
                   pos.x, pos.y, action_points.current, action_points.max);
@@ -0,0 +1,157 @@
+/// Noise and stealth systems
+///
+/// Actions emit `NoiseEvent`s that spread over walkable tiles, losing
+/// loudness with distance. Monsters that hear a noise may wake up and
+/// investigate, or spot the player straight away if it was loud enough.
+
+use bevy::prelude::*;
+use rand::Rng;
+use crate::components::{Player, Position, Viewshed, Enemy, AiState, Sneaking, Faction, Grudges};
+use crate::resources::{
+    CurrentMap, DijkstraMap, CombatLog, FactionTable, Allegiance, ActionInput, PlayerAction, GameRng,
+};
+use crate::systems::combat::{AttackMissed, DamageDealt};
+use crate::systems::movement::EntityMoved;
+use crate::constants::{
+    NOISE_WALK, NOISE_SNEAK, NOISE_COMBAT, NOISE_ATTENUATION,
+    NOISE_WAKE_CHANCE_PER_VOLUME, NOISE_ALERT_VOLUME,
+};
+
+// ============================================================================
+// EVENTS
+// ============================================================================
+
+/// A sound made somewhere on the map
+#[derive(Event, Debug, Clone, Copy)]
+pub struct NoiseEvent {
+    pub origin: Position,
+    pub loudness: i32,
+}
+
+// ============================================================================
+// NOISE SOURCES
+// ============================================================================
+
+/// Turn player footsteps and combat into noise
+pub fn emit_action_noise_system(
+    mut moved_events: EventReader<EntityMoved>,
+    mut missed_events: EventReader<AttackMissed>,
+    mut damage_events: EventReader<DamageDealt>,
+    mut noise_events: EventWriter<NoiseEvent>,
+    player_query: Query<Option<&Sneaking>, With<Player>>,
+    position_query: Query<&Position>,
+) {
+    // Footsteps (monster movement is assumed to be quiet)
+    for event in moved_events.read() {
+        if let Ok(sneaking) = player_query.get(event.entity) {
+            let loudness = if sneaking.is_some() { NOISE_SNEAK } else { NOISE_WALK };
+            noise_events.send(NoiseEvent { origin: event.to, loudness });
+        }
+    }
+
+    // Clashing weapons, whether or not the blow lands
+    let combat_targets = missed_events
+        .read()
+        .map(|event| event.defender)
+        .chain(damage_events.read().filter(|event| event.source.is_some()).map(|event| event.target));
+
+    for target in combat_targets {
+        if let Ok(pos) = position_query.get(target) {
+            noise_events.send(NoiseEvent { origin: *pos, loudness: NOISE_COMBAT });
+        }
+    }
+}
+
+// ============================================================================
+// HEARING
+// ============================================================================
+
+/// Let unaware monsters react to the noises they can hear
+#[allow(clippy::type_complexity)]
+pub fn hear_noise_system(
+    mut noise_events: EventReader<NoiseEvent>,
+    mut combat_log: ResMut<CombatLog>,
+    mut rng: ResMut<GameRng>,
+    map: Res<CurrentMap>,
+    factions: Res<FactionTable>,
+    player_query: Query<(Entity, &Position, &Faction, Option<&Grudges>), (With<Player>, Without<Enemy>)>,
+    mut enemy_query: Query<(Entity, &Position, &Viewshed, &Faction, Option<&Grudges>, &mut AiState), With<Enemy>>,
+) {
+    let player = player_query.get_single().ok();
+
+    for noise in noise_events.read() {
+        // Distance along walkable tiles, so walls muffle sound; only as far
+        // as the noise carries
+        let distances = DijkstraMap::build_within(&map, &[noise.origin], noise.loudness / NOISE_ATTENUATION);
+
+        for (entity, pos, viewshed, faction, grudges, mut state) in enemy_query.iter_mut() {
+            if state.is_aware() {
+                continue;
+            }
+
+            let Some(distance) = distances.get(*pos) else {
+                continue;
+            };
+            let volume = noise.loudness - distance * NOISE_ATTENUATION;
+            if volume <= 0 {
+                continue;
+            }
+
+            // Sleepers may sleep through quiet noises
+            if *state == AiState::Asleep {
+                let wake_chance = volume as u32 * NOISE_WAKE_CHANCE_PER_VOLUME;
+                if rng.gen_range(0..100) >= wake_chance {
+                    continue;
+                }
+            }
+
+            // A loud noise makes a monster notice a hostile player it can see
+            let hostile_player_in_view = player.filter(|(player, player_pos, player_faction, player_grudges)| {
+                viewshed.can_see(player_pos)
+                    && factions.is_hostile(
+                        Allegiance::new(entity, faction, grudges),
+                        Allegiance::new(*player, player_faction, *player_grudges),
+                    )
+            });
+
+            *state = match hostile_player_in_view {
+                Some((_, player_pos, ..)) if volume >= NOISE_ALERT_VOLUME => {
+                    AiState::Hunting { last_known: *player_pos }
+                }
+                _ => AiState::Investigating { target: noise.origin },
+            };
+        }
+
+        if noise.loudness >= NOISE_ALERT_VOLUME * 3 {
+            combat_log.add_message("A loud noise echoes through the dungeon!".to_string());
+        }
+    }
+}
+
+// ============================================================================
+// SNEAK MODE
+// ============================================================================
+
+/// Toggle sneak mode (C by default)
+pub fn toggle_sneak_system(
+    mut commands: Commands,
+    actions: Res<ActionInput>,
+    mut combat_log: ResMut<CombatLog>,
+    player_query: Query<(Entity, Option<&Sneaking>), With<Player>>,
+) {
+    if !actions.just_pressed(PlayerAction::ToggleSneak) {
+        return;
+    }
+
+    let Ok((player, sneaking)) = player_query.get_single() else {
+        return;
+    };
+
+    if sneaking.is_some() {
+        commands.entity(player).remove::<Sneaking>();
+        combat_log.add_message("You stop sneaking.".to_string());
+    } else {
+        commands.entity(player).insert(Sneaking);
+        combat_log.add_message("You start sneaking.".to_string());
+    }
+}
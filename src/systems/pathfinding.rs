@@ -0,0 +1,34 @@
+/// Pathfinding systems - keeps the shared Dijkstra maps up to date
+
+use bevy::prelude::*;
+use crate::components::{Player, Position};
+use crate::resources::{CurrentMap, DijkstraMap, DijkstraMaps};
+
+// ============================================================================
+// DIJKSTRA MAP UPDATES
+// ============================================================================
+
+/// Rebuild the approach and flee maps when the player moves or the map changes
+///
+/// Runs at the start of the enemy turn, so it also drops last turn's goal maps.
+pub fn update_dijkstra_maps_system(
+    player_query: Query<Ref<Position>, With<Player>>,
+    map: Res<CurrentMap>,
+    mut dijkstra_maps: ResMut<DijkstraMaps>,
+) {
+    let player_pos = match player_query.get_single() {
+        Ok(pos) => pos,
+        Err(_) => return,
+    };
+
+    dijkstra_maps.goals.clear();
+
+    let stale = dijkstra_maps.approach.is_none() || player_pos.is_changed() || map.is_changed();
+    if !stale {
+        return;
+    }
+
+    let approach = DijkstraMap::build(&map, &[*player_pos]);
+    dijkstra_maps.flee = Some(approach.flee_map(&map));
+    dijkstra_maps.approach = Some(approach);
+}
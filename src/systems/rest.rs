@@ -0,0 +1,136 @@
+/// Waiting, resting and natural healing
+
+use bevy::prelude::*;
+use crate::components::{Player, Health, Regeneration};
+use crate::resources::{PlayerActionPoints, CombatLog, FactionTable, ActionInput, PlayerAction};
+use crate::systems::explore::{PlayerSight, OtherActor, visible_hostile};
+
+// ============================================================================
+// RESOURCES
+// ============================================================================
+
+/// Whether the player is resting, and their health when it last checked
+#[derive(Resource, Default)]
+pub struct Resting {
+    pub active: bool,
+    pub last_health: i32,
+}
+
+impl Resting {
+    pub fn stop(&mut self, combat_log: &mut CombatLog, reason: impl Into<String>) {
+        self.active = false;
+        combat_log.add_message(reason.into());
+    }
+}
+
+// ============================================================================
+// WAITING
+// ============================================================================
+
+/// Pass the turn (Z or numpad 5 by default), spending all remaining action points
+pub fn player_wait_input_system(
+    actions: Res<ActionInput>,
+    mut action_points: ResMut<PlayerActionPoints>,
+) {
+    if actions.just_pressed(PlayerAction::Wait) {
+        let remaining = action_points.current;
+        action_points.spend(remaining);
+    }
+}
+
+// ============================================================================
+// RESTING
+// ============================================================================
+
+/// Start resting until healed (R by default), unless enemies are in sight
+pub fn rest_input_system(
+    actions: Res<ActionInput>,
+    mut resting: ResMut<Resting>,
+    mut combat_log: ResMut<CombatLog>,
+    factions: Res<FactionTable>,
+    player_query: Query<PlayerSight, With<Player>>,
+    actor_query: Query<OtherActor, Without<Player>>,
+) {
+    if !actions.just_pressed(PlayerAction::Rest) || resting.active {
+        return;
+    }
+    let Ok(player) = player_query.get_single() else {
+        return;
+    };
+    let health = player.3;
+
+    if health.current >= health.max {
+        combat_log.add_message("You are already at full health.".to_string());
+        return;
+    }
+    if let Some(name) = visible_hostile(&factions, &player, actor_query.iter()) {
+        combat_log.add_message(format!("You can't rest with {} in sight!", name.0));
+        return;
+    }
+
+    resting.active = true;
+    resting.last_health = health.current;
+    combat_log.add_message("You rest.".to_string());
+}
+
+/// Wait out turns while resting
+///
+/// Enemies still take their turns in between. Resting stops on any other
+/// player action, when a hostile comes into view, when the player is hurt,
+/// or once they are back to full health.
+pub fn rest_turn_system(
+    actions: Res<ActionInput>,
+    mut resting: ResMut<Resting>,
+    mut action_points: ResMut<PlayerActionPoints>,
+    mut combat_log: ResMut<CombatLog>,
+    factions: Res<FactionTable>,
+    player_query: Query<PlayerSight, With<Player>>,
+    actor_query: Query<OtherActor, Without<Player>>,
+) {
+    if !resting.active {
+        return;
+    }
+
+    let interrupted = PlayerAction::ALL
+        .into_iter()
+        .any(|action| action != PlayerAction::Rest && actions.just_pressed(action));
+    if interrupted {
+        resting.active = false;
+        return;
+    }
+
+    let Ok(player) = player_query.get_single() else {
+        return;
+    };
+    let health = player.3;
+
+    if let Some(name) = visible_hostile(&factions, &player, actor_query.iter()) {
+        resting.stop(&mut combat_log, format!("{} comes into view!", name.0));
+        return;
+    }
+    if health.current < resting.last_health {
+        resting.stop(&mut combat_log, "You are hurt and stop resting.");
+        return;
+    }
+    if health.current >= health.max {
+        resting.stop(&mut combat_log, "You feel rested.");
+        return;
+    }
+    resting.last_health = health.current;
+
+    let remaining = action_points.current;
+    action_points.spend(remaining);
+}
+
+// ============================================================================
+// REGENERATION
+// ============================================================================
+
+/// Heal everything that regenerates, once per turn (runs OnEnter PlayerTurn)
+pub fn natural_regeneration_system(mut query: Query<(&mut Health, &mut Regeneration)>) {
+    for (mut health, mut regeneration) in query.iter_mut() {
+        if health.current < health.max && regeneration.tick() {
+            health.heal(1);
+        }
+    }
+}
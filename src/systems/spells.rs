@@ -0,0 +1,144 @@
+/// Monster spell systems: bolts, heals, blinks, summons and novas
+
+use bevy::prelude::*;
+use std::collections::HashSet;
+use rand::Rng;
+use crate::components::{Position, Health, Name, AiState, Spell, SpellKind, SummonedBy, Faction, Grudges};
+use crate::resources::{CurrentMap, CombatLog, Bestiary, FactionTable, Allegiance, GameRng, grid_distance};
+use crate::systems::combat::{AttackIntent, HealApplied, CombatSequence};
+use crate::systems::movement::EntityMoved;
+use crate::systems::enemy_spawning::spawn_monster;
+use crate::resources::dijkstra::NEIGHBOURS;
+use crate::constants::BLINK_MIN_DISTANCE;
+
+// ============================================================================
+// EVENTS
+// ============================================================================
+
+/// Request for a monster to cast one of its spells
+///
+/// `target` is the victim of a bolt, the ally to heal, or the threat to
+/// blink away from / summon against.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct CastSpell {
+    pub caster: Entity,
+    pub spell: Spell,
+    pub target: Entity,
+}
+
+// ============================================================================
+// SPELL RESOLUTION
+// ============================================================================
+
+/// Apply the effects of every spell cast this turn
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn resolve_spell_system(
+    mut commands: Commands,
+    mut cast_events: EventReader<CastSpell>,
+    mut combat_log: ResMut<CombatLog>,
+    mut attack_intents: EventWriter<AttackIntent>,
+    mut heal_events: EventWriter<HealApplied>,
+    mut sequence: ResMut<CombatSequence>,
+    mut moved_events: EventWriter<EntityMoved>,
+    mut rng: ResMut<GameRng>,
+    map: Res<CurrentMap>,
+    bestiary: Res<Bestiary>,
+    factions: Res<FactionTable>,
+    mut actors: Query<(Entity, &mut Position, &mut Health, &Name, &Faction, Option<&Grudges>)>,
+) {
+    for cast in cast_events.read() {
+        let (caster_pos, caster_name) = match actors.get(cast.caster) {
+            Ok((_, pos, health, name, ..)) if !health.is_dead() => (*pos, name.0.clone()),
+            _ => continue,
+        };
+        let target_pos = match actors.get(cast.target) {
+            Ok((_, pos, ..)) => *pos,
+            Err(_) => continue,
+        };
+
+        combat_log.add_message(format!("{} casts {}!", caster_name, cast.spell.name));
+
+        match cast.spell.kind {
+            SpellKind::Bolt { damage_type } => {
+                attack_intents.send(AttackIntent::typed(cast.caster, cast.target, damage_type));
+            }
+            SpellKind::HealAlly { amount } => {
+                if let Ok((_, _, mut health, ..)) = actors.get_mut(cast.target) {
+                    let before = health.current;
+                    health.heal(amount);
+                    heal_events.send(HealApplied {
+                        source: Some(cast.caster),
+                        target: cast.target,
+                        amount: health.current - before,
+                        seq: sequence.advance(),
+                    });
+                }
+            }
+            SpellKind::Blink => {
+                let occupied: HashSet<Position> = actors.iter().map(|(_, pos, ..)| *pos).collect();
+
+                // Any free tile in range that puts distance between caster and threat
+                let candidates: Vec<Position> = (-cast.spell.range..=cast.spell.range)
+                    .flat_map(|dy| (-cast.spell.range..=cast.spell.range).map(move |dx| (dx, dy)))
+                    .map(|(dx, dy)| Position::new(caster_pos.x + dx, caster_pos.y + dy))
+                    .filter(|pos| map.is_walkable(pos.x, pos.y) && !occupied.contains(pos))
+                    .filter(|pos| grid_distance(*pos, target_pos) >= BLINK_MIN_DISTANCE)
+                    .collect();
+
+                if candidates.is_empty() {
+                    combat_log.add_message(format!("{}'s spell fizzles.", caster_name));
+                    continue;
+                }
+
+                let destination = candidates[rng.gen_range(0..candidates.len())];
+                if let Ok((_, mut pos, ..)) = actors.get_mut(cast.caster) {
+                    *pos = destination;
+                    moved_events.send(EntityMoved { entity: cast.caster, from: caster_pos, to: destination });
+                }
+            }
+            SpellKind::Summon { minion, .. } => {
+                let Some(template) = bestiary.get(minion) else {
+                    warn!("Summon spell refers to unknown monster template '{}'", minion);
+                    continue;
+                };
+
+                let occupied: HashSet<Position> = actors.iter().map(|(_, pos, ..)| *pos).collect();
+                let free_tile = NEIGHBOURS
+                    .iter()
+                    .map(|(dx, dy)| Position::new(caster_pos.x + dx, caster_pos.y + dy))
+                    .find(|pos| map.is_walkable(pos.x, pos.y) && !occupied.contains(pos));
+
+                let Some(spawn_pos) = free_tile else {
+                    combat_log.add_message(format!("{}'s spell fizzles.", caster_name));
+                    continue;
+                };
+
+                let minion_entity = spawn_monster(
+                    &mut commands,
+                    template,
+                    spawn_pos,
+                    format!("Summoned {}", template.name),
+                    AiState::Hunting { last_known: target_pos },
+                );
+                commands.entity(minion_entity).insert(SummonedBy(cast.caster));
+                combat_log.add_message(format!("A {} rises from the ground!", template.name));
+            }
+            SpellKind::Nova { damage_type, radius } => {
+                let Ok((caster, _, _, _, caster_faction, caster_grudges)) = actors.get(cast.caster) else {
+                    continue;
+                };
+                let caster = Allegiance::new(caster, caster_faction, caster_grudges);
+
+                // Every hostile in the blast gets its own attack roll
+                for (victim, pos, health, _, faction, grudges) in actors.iter() {
+                    if !health.is_dead()
+                        && grid_distance(caster_pos, *pos) <= radius
+                        && factions.is_hostile(caster, Allegiance::new(victim, faction, grudges))
+                    {
+                        attack_intents.send(AttackIntent::typed(cast.caster, victim, damage_type));
+                    }
+                }
+            }
+        }
+    }
+}
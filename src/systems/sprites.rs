@@ -0,0 +1,62 @@
+/// Sprite systems - giving renderable entities a sprite and keeping it on their tile
+
+use bevy::prelude::*;
+use crate::components::{Player, Position, Renderable};
+use crate::systems::fov::MapTile;
+use crate::systems::tileset::spawn_glyph_background;
+use crate::constants::TILE_SIZE;
+
+/// World-space position of a tile's centre on a draw layer
+pub fn grid_to_world(pos: Position, layer: f32) -> Vec3 {
+    Vec3::new(pos.x as f32 * TILE_SIZE, pos.y as f32 * TILE_SIZE, layer)
+}
+
+/// Add a sprite to every new renderable entity
+///
+/// The game rules only spawn `Renderable`s; this is where they become
+/// something to draw. Map tiles start black (unseen) and get a glyph
+/// background if they have one; everything but the player starts hidden
+/// until the FOV systems reveal it.
+#[allow(clippy::type_complexity)]
+pub fn attach_sprites_system(
+    mut commands: Commands,
+    query: Query<(Entity, &Renderable, Option<&Position>, Option<&MapTile>, Has<Player>), Without<Sprite>>,
+) {
+    for (entity, renderable, pos, tile, is_player) in query.iter() {
+        let Some(pos) = pos.or(tile.map(|tile| &tile.position)) else {
+            continue;
+        };
+
+        let mut sprite = commands.entity(entity);
+        sprite.insert((
+            Sprite {
+                color: if tile.is_some() { Color::BLACK } else { renderable.color },
+                custom_size: Some(Vec2::splat(TILE_SIZE * renderable.size)),
+                ..default()
+            },
+            Transform::from_translation(grid_to_world(*pos, renderable.layer)),
+        ));
+
+        let background = renderable.background;
+        match tile {
+            Some(_) if background != Color::NONE => {
+                sprite.with_children(|tile| spawn_glyph_background(tile, background));
+            }
+            Some(_) => {}
+            None if !is_player => {
+                sprite.insert(Visibility::Hidden);
+            }
+            None => {}
+        }
+    }
+}
+
+/// Update sprite positions based on grid Position component
+pub fn update_sprite_positions_system(
+    mut query: Query<(&Position, &mut Transform), Changed<Position>>,
+) {
+    for (pos, mut transform) in query.iter_mut() {
+        transform.translation.x = pos.x as f32 * TILE_SIZE;
+        transform.translation.y = pos.y as f32 * TILE_SIZE;
+    }
+}
@@ -0,0 +1,263 @@
+/// Tileset systems - loading the sprite sheets, switching between tiles and
+/// glyphs, and applying the current art to sprites
+
+use bevy::prelude::*;
+use bevy::asset::io::file::FileAssetReader;
+use std::collections::HashSet;
+use crate::components::Renderable;
+use crate::resources::{
+    Tileset, TilesetAtlas, RenderMode, VisibilityMap, VisibilityState, CombatLog, cp437_index,
+};
+use crate::systems::fov::{MapTile, dimmed};
+use crate::constants::{
+    TILESET_FILE, TILESET_TILE_PIXELS, TILESET_COLUMNS, TILESET_ROWS, GLYPH_FONT_FILE, GLYPH_FONT_PIXELS,
+    TILE_SIZE,
+};
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Sprite behind a map tile's glyph, shown only in glyph mode
+#[derive(Component)]
+pub struct GlyphBackground;
+
+/// Text drawing an entity's glyph in glyph mode when there is no glyph font
+#[derive(Component)]
+pub struct GlyphText;
+
+// ============================================================================
+// LOADING
+// ============================================================================
+
+/// Whether `file` is in the assets folder the asset server reads from
+///
+/// That folder sits under `BEVY_ASSET_ROOT`, the manifest directory when run
+/// through cargo, or next to the executable - not the working directory.
+fn asset_exists(file: &str) -> bool {
+    FileAssetReader::get_base_path()
+        .join(AssetPlugin::default().file_path)
+        .join(file)
+        .exists()
+}
+
+/// Load a grid sprite sheet from the assets folder, if it is there
+fn load_atlas(
+    asset_server: &AssetServer,
+    layouts: &mut Assets<TextureAtlasLayout>,
+    file: &str,
+    cell_pixels: u32,
+    columns: u32,
+    rows: u32,
+) -> Option<TilesetAtlas> {
+    if !asset_exists(file) {
+        info!("No assets/{} found", file);
+        return None;
+    }
+
+    let layout = TextureAtlasLayout::from_grid(UVec2::splat(cell_pixels), columns, rows, None, None);
+    info!("Loaded {}", file);
+    Some(TilesetAtlas {
+        image: asset_server.load(file.to_string()),
+        layout: layouts.add(layout),
+    })
+}
+
+/// Load the tileset and glyph font from the assets folder, if they are there
+pub fn load_tileset_system(
+    asset_server: Res<AssetServer>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut tileset: ResMut<Tileset>,
+) {
+    tileset.atlas = load_atlas(
+        &asset_server,
+        &mut layouts,
+        TILESET_FILE,
+        TILESET_TILE_PIXELS,
+        TILESET_COLUMNS,
+        TILESET_ROWS,
+    );
+    tileset.glyph_font = load_atlas(&asset_server, &mut layouts, GLYPH_FONT_FILE, GLYPH_FONT_PIXELS, 16, 16);
+}
+
+// ============================================================================
+// MODE SWITCHING
+// ============================================================================
+
+/// Switch between tileset and glyph rendering with F2
+pub fn toggle_render_mode_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    tileset: Res<Tileset>,
+    mut mode: ResMut<RenderMode>,
+    mut combat_log: ResMut<CombatLog>,
+) {
+    if !keyboard.just_pressed(KeyCode::F2) {
+        return;
+    }
+
+    match *mode {
+        RenderMode::Tiles => {
+            if tileset.glyph_font.is_none() {
+                combat_log.add_message(format!("No assets/{}; drawing glyphs as text.", GLYPH_FONT_FILE));
+            }
+            *mode = RenderMode::Glyphs;
+        }
+        RenderMode::Glyphs => *mode = RenderMode::Tiles,
+    }
+}
+
+// ============================================================================
+// APPLYING ART
+// ============================================================================
+
+/// Point sprites at their tileset entry or glyph, or back to plain colored squares
+///
+/// Sprite colors are left alone, so fog of war and lighting tint the tiles.
+pub fn apply_tileset_system(
+    tileset: Res<Tileset>,
+    mode: Res<RenderMode>,
+    mut query: Query<(Ref<Renderable>, &mut Sprite)>,
+) {
+    let art_changed = tileset.is_changed() || mode.is_changed();
+
+    for (renderable, mut sprite) in query.iter_mut() {
+        if !art_changed && !renderable.is_changed() && !sprite.is_added() {
+            continue;
+        }
+
+        let art = match *mode {
+            RenderMode::Tiles => tileset.atlas.as_ref().zip(renderable.tile),
+            RenderMode::Glyphs => tileset.glyph_font.as_ref().map(|font| (font, cp437_index(renderable.glyph))),
+        };
+
+        // Glyphs without a font are drawn by a `GlyphText` child instead
+        let size = match *mode {
+            RenderMode::Glyphs if tileset.glyph_font.is_none() => Vec2::ZERO,
+            _ => Vec2::splat(TILE_SIZE * renderable.size),
+        };
+        if sprite.custom_size != Some(size) {
+            sprite.custom_size = Some(size);
+        }
+
+        match art {
+            Some((atlas, index)) => {
+                sprite.image = atlas.image.clone();
+                sprite.texture_atlas = Some(TextureAtlas {
+                    layout: atlas.layout.clone(),
+                    index,
+                });
+            }
+            None => {
+                sprite.image = Handle::default();
+                sprite.texture_atlas = None;
+            }
+        }
+    }
+}
+
+/// Draw glyphs as text in glyph mode when there is no glyph font
+///
+/// Each sprite gets a `GlyphText` child in its glyph and (tinted) color; the
+/// children are removed again when leaving glyph mode.
+#[allow(clippy::type_complexity)]
+pub fn update_glyph_text_system(
+    mut commands: Commands,
+    tileset: Res<Tileset>,
+    mode: Res<RenderMode>,
+    sprite_query: Query<(Entity, Ref<Renderable>, Ref<Sprite>)>,
+    mut text_query: Query<(Entity, &Parent, &mut Text2d, &mut TextColor), With<GlyphText>>,
+) {
+    let as_text = *mode == RenderMode::Glyphs && tileset.glyph_font.is_none();
+    if !as_text {
+        if mode.is_changed() || tileset.is_changed() {
+            for (text, ..) in text_query.iter() {
+                commands.entity(text).despawn();
+            }
+        }
+        return;
+    }
+
+    // Keep existing glyphs in step with their sprite
+    let mut drawn = HashSet::new();
+    for (_, parent, mut text, mut color) in text_query.iter_mut() {
+        let Ok((entity, renderable, sprite)) = sprite_query.get(parent.get()) else {
+            continue;
+        };
+        drawn.insert(entity);
+        if renderable.is_changed() {
+            text.0 = renderable.glyph.to_string();
+        }
+        if sprite.is_changed() && color.0 != sprite.color {
+            color.0 = sprite.color;
+        }
+    }
+
+    for (entity, renderable, sprite) in sprite_query.iter() {
+        if drawn.contains(&entity) {
+            continue;
+        }
+        commands.entity(entity).with_children(|parent| {
+            parent.spawn((
+                GlyphText,
+                Text2d::new(renderable.glyph.to_string()),
+                TextFont {
+                    font_size: TILE_SIZE * renderable.size,
+                    ..default()
+                },
+                TextColor(sprite.color),
+                // Just in front of the (empty) sprite
+                Transform::from_xyz(0.0, 0.0, 0.1),
+            ));
+        });
+    }
+}
+
+/// Spawn the background sprite for a map tile whose glyph has one
+pub fn spawn_glyph_background(parent: &mut ChildBuilder, background: Color) {
+    parent.spawn((
+        GlyphBackground,
+        Sprite {
+            color: background,
+            custom_size: Some(Vec2::new(TILE_SIZE, TILE_SIZE)),
+            ..default()
+        },
+        // Just behind the glyph
+        Transform::from_xyz(0.0, 0.0, -0.1),
+        Visibility::Hidden,
+    ));
+}
+
+/// Show glyph backgrounds in glyph mode, dimmed and hidden like their tiles
+#[allow(clippy::type_complexity)]
+pub fn update_glyph_backgrounds_system(
+    mode: Res<RenderMode>,
+    visibility_map: Res<VisibilityMap>,
+    tile_query: Query<(&MapTile, &Renderable, &Children)>,
+    mut background_query: Query<(&mut Sprite, &mut Visibility), With<GlyphBackground>>,
+) {
+    if !mode.is_changed() && !visibility_map.is_changed() {
+        return;
+    }
+
+    for (tile, renderable, children) in tile_query.iter() {
+        let shown = match visibility_map.get(&tile.position) {
+            _ if *mode != RenderMode::Glyphs => None,
+            VisibilityState::Unseen => None,
+            VisibilityState::Explored => Some(dimmed(renderable.background)),
+            VisibilityState::Visible => Some(renderable.background),
+        };
+
+        for child in children.iter() {
+            let Ok((mut sprite, mut visibility)) = background_query.get_mut(*child) else {
+                continue;
+            };
+            match shown {
+                Some(color) => {
+                    sprite.color = color;
+                    *visibility = Visibility::Inherited;
+                }
+                None => *visibility = Visibility::Hidden,
+            }
+        }
+    }
+}
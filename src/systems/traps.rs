@@ -0,0 +1,372 @@
+/// Trap systems: placement, detection, triggering and disarming
+
+use bevy::prelude::*;
+use bevy::ecs::event::EventCursor;
+use std::collections::HashSet;
+use rand::Rng;
+use crate::components::{
+    Player, Position, Viewshed, Health, DamageType, Resistances, Name, Trap, TrapKind, Concealed,
+    Renderable, Memorable,
+};
+use crate::resources::{CurrentMap, PlayerActionPoints, CombatLog, ActionInput, PlayerAction, GameRng};
+use crate::systems::movement::EntityMoved;
+use crate::systems::combat::{DamageDealt, EntityKilled, AttackOutcome, CombatSequence};
+use crate::systems::noise::NoiseEvent;
+use crate::constants::*;
+
+// ============================================================================
+// RESOURCES
+// ============================================================================
+
+/// Pending disarm target (mirrors PendingAttack pattern)
+#[derive(Resource, Default)]
+pub struct PendingDisarm {
+    pub target: Option<Entity>,
+}
+
+// ============================================================================
+// TRAP PLACEMENT
+// ============================================================================
+
+/// Place 2-4 concealed traps at random walkable positions outside the boss
+/// arena, away from the `occupied` tiles (the player and their companions)
+pub fn spawn_traps_system(
+    mut commands: Commands,
+    map: &CurrentMap,
+    occupied: &HashSet<Position>,
+    rng: &mut GameRng,
+) {
+    let count = rng.gen_range(TRAP_MIN_COUNT..=TRAP_MAX_COUNT);
+
+    info!("Placing {} traps", count);
+
+    let spots: Vec<Position> = std::iter::repeat_with(|| map.random_walkable_position(rng))
+        .take(count * 100)
+        .flatten()
+        .filter(|pos| !occupied.contains(pos) && !map.in_arena(*pos))
+        .take(count)
+        .collect();
+    if spots.len() < count {
+        warn!("Only found room for {} of {} traps", spots.len(), count);
+    }
+
+    for pos in spots {
+        let kind = TrapKind::ALL[rng.gen_range(0..TrapKind::ALL.len())];
+
+        commands.spawn((
+            Trap::new(kind),
+            Concealed,
+            pos,
+            Name::new(kind.name()),
+            Renderable::new(kind.color())
+                .with_tile(kind.tile())
+                .with_glyph(kind.glyph())
+                .with_size(0.5)
+                .with_layer(Z_LAYER_ITEMS),
+            Memorable,
+        ));
+
+        info!("Placed {} at ({}, {})", kind.name(), pos.x, pos.y);
+    }
+}
+
+// ============================================================================
+// DETECTION
+// ============================================================================
+
+/// Roll perception for every concealed trap inside the player's viewshed
+pub fn detect_traps_system(
+    mut commands: Commands,
+    mut combat_log: ResMut<CombatLog>,
+    mut rng: ResMut<GameRng>,
+    player_query: Query<&Viewshed, (With<Player>, Changed<Viewshed>)>,
+    trap_query: Query<(Entity, &Trap, &Position), With<Concealed>>,
+) {
+    // Only roll when the player has a fresh look around
+    let viewshed = match player_query.get_single() {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    for (entity, trap, pos) in trap_query.iter() {
+        if !viewshed.can_see(pos) {
+            continue;
+        }
+
+        let roll = rng.gen_range(0..100);
+        if roll < TRAP_DETECTION_CHANCE {
+            commands.entity(entity).remove::<Concealed>();
+            combat_log.add_message(format!("You spot a {}!", trap.kind.name()));
+        }
+    }
+}
+
+// ============================================================================
+// TRIGGERING
+// ============================================================================
+
+/// Set off traps on tiles that actors have just stepped onto
+#[allow(clippy::too_many_arguments)]
+pub fn trigger_traps_system(
+    mut commands: Commands,
+    mut moved_events: ResMut<Events<EntityMoved>>,
+    mut moved_cursor: Local<EventCursor<EntityMoved>>,
+    mut combat_log: ResMut<CombatLog>,
+    mut damage_events: EventWriter<DamageDealt>,
+    mut killed_events: EventWriter<EntityKilled>,
+    mut sequence: ResMut<CombatSequence>,
+    mut noise_events: EventWriter<NoiseEvent>,
+    mut rng: ResMut<GameRng>,
+    map: Res<CurrentMap>,
+    trap_query: Query<(Entity, &Trap, &Position), Without<Health>>,
+    mut actor_query: Query<(&mut Position, &mut Health, &Name, Option<&Resistances>), Without<Trap>>,
+) {
+    // Collected first, so teleports can send moves of their own (read next time round)
+    let moves: Vec<EntityMoved> = moved_cursor.read(&moved_events).copied().collect();
+
+    for event in moves {
+        for (trap_entity, trap, trap_pos) in trap_query.iter() {
+            if *trap_pos != event.to {
+                continue;
+            }
+
+            let Ok((mut actor_pos, mut health, name, resistances)) = actor_query.get_mut(event.entity) else {
+                continue;
+            };
+
+            // Dead actors don't set off anything
+            if health.is_dead() {
+                continue;
+            }
+
+            // A sprung trap is no longer a secret
+            commands.entity(trap_entity).remove::<Concealed>();
+
+            if trap.kind == TrapKind::Alarm {
+                noise_events.send(NoiseEvent { origin: *trap_pos, loudness: NOISE_ALARM });
+            }
+
+            let damage = spring_trap(
+                trap.kind,
+                &name.0,
+                &mut actor_pos,
+                &map,
+                &mut combat_log,
+                &mut rng,
+            );
+
+            // Teleported: the landing tile counts as stepped on too
+            if *actor_pos != event.to {
+                moved_events.send(EntityMoved { entity: event.entity, from: event.to, to: *actor_pos });
+            }
+
+            if let Some((amount, damage_type)) = damage {
+                report_trap_damage(
+                    event.entity,
+                    amount,
+                    damage_type,
+                    resistances,
+                    &mut health,
+                    &mut damage_events,
+                    &mut killed_events,
+                    &mut sequence,
+                );
+            }
+        }
+    }
+}
+
+/// Apply a trap's non-damage effects and describe it; returns damage to deal
+fn spring_trap(
+    kind: TrapKind,
+    victim_name: &str,
+    victim_pos: &mut Position,
+    map: &CurrentMap,
+    combat_log: &mut CombatLog,
+    rng: &mut GameRng,
+) -> Option<(i32, DamageType)> {
+    match kind {
+        TrapKind::Dart => {
+            combat_log.add_message(format!("A poisoned dart shoots out at {}!", victim_name));
+            Some((TRAP_DART_DAMAGE, DamageType::Poison))
+        }
+        TrapKind::Pit => {
+            combat_log.add_message(format!("{} falls into a pit!", victim_name));
+            Some((TRAP_PIT_DAMAGE, DamageType::Physical))
+        }
+        TrapKind::Teleport => {
+            match map.random_walkable_position(rng) {
+                Some(destination) => {
+                    *victim_pos = destination;
+                    combat_log.add_message(format!(
+                        "{} is teleported away!", victim_name
+                    ));
+                }
+                None => {
+                    combat_log.add_message("The teleport trap fizzles.".to_string());
+                }
+            }
+            None
+        }
+        TrapKind::Alarm => {
+            combat_log.add_message(format!(
+                "{} sets off an alarm! A loud bell rings out.", victim_name
+            ));
+            None
+        }
+    }
+}
+
+/// Apply trap damage (after resistances) and emit the matching combat events
+#[allow(clippy::too_many_arguments)]
+fn report_trap_damage(
+    victim: Entity,
+    damage: i32,
+    damage_type: DamageType,
+    resistances: Option<&Resistances>,
+    health: &mut Health,
+    damage_events: &mut EventWriter<DamageDealt>,
+    killed_events: &mut EventWriter<EntityKilled>,
+    sequence: &mut CombatSequence,
+) {
+    let (damage, resistance) = match resistances {
+        Some(resistances) => resistances.apply(damage, damage_type),
+        None => (damage, None),
+    };
+
+    health.take_damage(damage);
+
+    damage_events.send(DamageDealt {
+        source: None,
+        target: victim,
+        amount: damage,
+        damage_type,
+        outcome: AttackOutcome::Hit,
+        resistance,
+        remaining: health.current,
+        kind: None,
+        seq: sequence.advance(),
+    });
+
+    if health.is_dead() {
+        killed_events.send(EntityKilled { entity: victim, killer: None, seq: sequence.advance() });
+    }
+}
+
+// ============================================================================
+// DISARMING
+// ============================================================================
+
+/// Capture the disarm action and queue a disarm on a known trap under or next to the player
+pub fn player_disarm_input_system(
+    actions: Res<ActionInput>,
+    mut pending_disarm: ResMut<PendingDisarm>,
+    action_points: Res<PlayerActionPoints>,
+    player_query: Query<&Position, With<Player>>,
+    trap_query: Query<(Entity, &Trap, &Position), Without<Concealed>>,
+) {
+    // Clear previous pending disarm
+    pending_disarm.target = None;
+
+    if !actions.just_pressed(PlayerAction::Disarm) {
+        return;
+    }
+
+    if !action_points.can_afford(DISARM_ACTION_COST) {
+        info!("Not enough action points to disarm! ({}/{})",
+              action_points.current, action_points.max);
+        return;
+    }
+
+    let player_pos = match player_query.get_single() {
+        Ok(pos) => pos,
+        Err(_) => return,
+    };
+
+    // Find a detected trap on or adjacent to the player's tile
+    for (trap_entity, trap, trap_pos) in trap_query.iter() {
+        let dx = (player_pos.x - trap_pos.x).abs();
+        let dy = (player_pos.y - trap_pos.y).abs();
+
+        if dx <= 1 && dy <= 1 {
+            pending_disarm.target = Some(trap_entity);
+            info!("Disarm queued: {} at ({}, {})", trap.kind.name(), trap_pos.x, trap_pos.y);
+            return;
+        }
+    }
+
+    info!("No known traps nearby to disarm!");
+}
+
+/// Execute pending disarm: roll success, remove the trap or possibly set it off
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn execute_disarm_system(
+    mut commands: Commands,
+    mut pending_disarm: ResMut<PendingDisarm>,
+    mut action_points: ResMut<PlayerActionPoints>,
+    mut combat_log: ResMut<CombatLog>,
+    mut damage_events: EventWriter<DamageDealt>,
+    mut killed_events: EventWriter<EntityKilled>,
+    mut sequence: ResMut<CombatSequence>,
+    mut rng: ResMut<GameRng>,
+    map: Res<CurrentMap>,
+    trap_query: Query<&Trap>,
+    mut player_query: Query<(Entity, &mut Position, &mut Health, &Name, Option<&Resistances>), With<Player>>,
+) {
+    let target = match pending_disarm.target.take() {
+        Some(t) => t,
+        None => return,
+    };
+
+    let trap = match trap_query.get(target) {
+        Ok(trap) => *trap,
+        Err(_) => {
+            info!("Trap no longer exists!");
+            return;
+        }
+    };
+
+    let (player, mut player_pos, mut player_health, player_name, player_resistances) = match player_query.get_single_mut() {
+        Ok(data) => data,
+        Err(_) => return,
+    };
+
+    let roll = rng.gen_range(0..100);
+    if roll < TRAP_DISARM_CHANCE {
+        combat_log.add_message(format!(
+            "{} disarms the {}.", player_name.0, trap.kind.name()
+        ));
+        commands.entity(target).despawn();
+    } else if roll >= 100 - TRAP_DISARM_FUMBLE_CHANCE {
+        combat_log.add_message(format!(
+            "{} fumbles and sets off the {}!", player_name.0, trap.kind.name()
+        ));
+        let damage = spring_trap(
+            trap.kind,
+            &player_name.0,
+            &mut player_pos,
+            &map,
+            &mut combat_log,
+            &mut rng,
+        );
+
+        if let Some((amount, damage_type)) = damage {
+            report_trap_damage(
+                player,
+                amount,
+                damage_type,
+                player_resistances,
+                &mut player_health,
+                &mut damage_events,
+                &mut killed_events,
+                &mut sequence,
+            );
+        }
+    } else {
+        combat_log.add_message(format!(
+            "{} fails to disarm the {}.", player_name.0, trap.kind.name()
+        ));
+    }
+
+    action_points.spend(DISARM_ACTION_COST);
+}
@@ -28,14 +28,13 @@ pub fn start_player_turn_system(
           action_points.current, action_points.max);
 }
 
-/// Handle enemy turn logic (runs during EnemyTurn state)
-/// Currently a stub - will be expanded in Phase 5 with enemy AI
+/// End the enemy turn (runs during EnemyTurn state)
+///
+/// The enemy AI systems are chained before this one, so by now every monster
+/// has acted; all that's left is to hand control back to the player.
 pub fn enemy_turn_system(
     mut next_state: ResMut<NextState<TurnState>>,
 ) {
-    // Stub: In Phase 5, this will coordinate enemy actions
-    // For now, immediately transition back to PlayerTurn
-
     info!("Enemy turn complete! Transitioning to PlayerTurn");
     next_state.set(TurnState::PlayerTurn);
 }
@@ -0,0 +1,304 @@
+/// UI systems: on-screen combat log panel and hover tooltip
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use crate::components::{
+    Player, Position, Viewshed, Name, Health, Concealed, AiState, CombatStats, Faction, Grudges,
+};
+use crate::resources::{
+    CombatLog, CurrentMap, VisibilityMap, VisibilityState, EntityMemory, FactionTable, Allegiance,
+};
+use crate::systems::mouse::HoveredTile;
+use crate::systems::combat::hit_distribution;
+use crate::constants::{
+    COLOR_UI_BACKGROUND, COLOR_UI_TEXT, COMBAT_LOG_VISIBLE_LINES, COMBAT_LOG_FONT_SIZE,
+};
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Marker for the combat log panel root node
+#[derive(Component)]
+pub struct CombatLogPanel;
+
+/// One text row of the combat log panel (0 = oldest visible row)
+#[derive(Component)]
+pub struct CombatLogLine(pub usize);
+
+/// Header text showing the scroll position
+#[derive(Component)]
+pub struct CombatLogHeader;
+
+/// Floating panel describing the hovered tile
+#[derive(Component)]
+pub struct HoverTooltip;
+
+// ============================================================================
+// SETUP
+// ============================================================================
+
+/// Spawn the combat log panel in the bottom-left corner
+pub fn spawn_combat_log_ui(mut commands: Commands) {
+    commands
+        .spawn((
+            CombatLogPanel,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(8.0),
+                bottom: Val::Px(8.0),
+                width: Val::Px(520.0),
+                padding: UiRect::all(Val::Px(6.0)),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(COLOR_UI_BACKGROUND),
+        ))
+        .with_children(|panel| {
+            panel.spawn((
+                CombatLogHeader,
+                Text::new("Combat Log"),
+                TextFont {
+                    font_size: COMBAT_LOG_FONT_SIZE,
+                    ..default()
+                },
+                TextColor(COLOR_UI_TEXT),
+            ));
+
+            for row in 0..COMBAT_LOG_VISIBLE_LINES {
+                panel.spawn((
+                    CombatLogLine(row),
+                    Text::new(""),
+                    TextFont {
+                        font_size: COMBAT_LOG_FONT_SIZE,
+                        ..default()
+                    },
+                    TextColor(COLOR_UI_TEXT),
+                ));
+            }
+        });
+}
+
+/// Spawn the (initially hidden) hover tooltip
+pub fn spawn_hover_tooltip_ui(mut commands: Commands) {
+    commands.spawn((
+        HoverTooltip,
+        Node {
+            position_type: PositionType::Absolute,
+            padding: UiRect::all(Val::Px(4.0)),
+            ..default()
+        },
+        BackgroundColor(COLOR_UI_BACKGROUND),
+        Text::new(""),
+        TextFont {
+            font_size: COMBAT_LOG_FONT_SIZE,
+            ..default()
+        },
+        TextColor(COLOR_UI_TEXT),
+        Visibility::Hidden,
+    ));
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// Scroll through log history with PageUp / PageDown (End jumps to latest)
+pub fn combat_log_scroll_input_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut combat_log: ResMut<CombatLog>,
+) {
+    if keyboard.just_pressed(KeyCode::PageUp) {
+        combat_log.scroll_up(COMBAT_LOG_VISIBLE_LINES);
+    }
+    if keyboard.just_pressed(KeyCode::PageDown) {
+        combat_log.scroll_down(COMBAT_LOG_VISIBLE_LINES);
+    }
+    if keyboard.just_pressed(KeyCode::End) {
+        combat_log.scroll_to_latest();
+    }
+}
+
+/// Refresh the log panel rows from the CombatLog resource
+pub fn update_combat_log_ui_system(
+    combat_log: Res<CombatLog>,
+    mut header_query: Query<&mut Text, (With<CombatLogHeader>, Without<CombatLogLine>)>,
+    mut line_query: Query<(&CombatLogLine, &mut Text, &mut TextColor)>,
+) {
+    // Only rebuild when the log changes
+    if !combat_log.is_changed() {
+        return;
+    }
+
+    let visible = combat_log.visible_entries(COMBAT_LOG_VISIBLE_LINES);
+
+    for (line, mut text, mut color) in line_query.iter_mut() {
+        match visible.get(line.0) {
+            Some(entry) => {
+                text.0 = entry.text.clone();
+                color.0 = entry.kind.color();
+            }
+            None => text.0.clear(),
+        }
+    }
+
+    if let Ok(mut header) = header_query.get_single_mut() {
+        header.0 = match combat_log.scroll_offset() {
+            0 => "Combat Log".to_string(),
+            offset => format!("Combat Log (scrolled back {}, End for latest)", offset),
+        };
+    }
+}
+
+// ============================================================================
+// TILE DESCRIPTIONS
+// ============================================================================
+
+/// Describe what the player knows about a tile, one line per thing
+///
+/// `visible_things` are descriptions of entities the player can currently see
+/// there; for tiles out of view, remembered things are listed instead.
+/// Returns None for tiles the player has never seen.
+pub fn describe_tile(
+    map: &CurrentMap,
+    visibility_map: &VisibilityMap,
+    memory: &EntityMemory,
+    pos: Position,
+    visible_things: &[String],
+) -> Option<Vec<String>> {
+    let tile = map.get_tile(pos.x, pos.y)?;
+
+    let mut lines = match visibility_map.get(&pos) {
+        VisibilityState::Unseen => return None,
+        VisibilityState::Explored => {
+            let mut lines = vec![format!("{} (remembered)", tile.name())];
+            lines.extend(memory.at(pos).iter().map(|thing| format!("{} (remembered)", thing.name)));
+            lines
+        }
+        VisibilityState::Visible => vec![tile.name().to_string()],
+    };
+    lines.extend(visible_things.iter().cloned());
+    Some(lines)
+}
+
+/// How hurt a creature looks
+pub fn wound_description(health: &Health) -> &'static str {
+    match health.percentage() {
+        p if p >= 1.0 => "unhurt",
+        p if p >= 0.75 => "lightly wounded",
+        p if p >= 0.5 => "wounded",
+        p if p >= 0.25 => "badly wounded",
+        _ => "almost dead",
+    }
+}
+
+/// Query data for describing the player's surroundings
+pub type Onlooker = (&'static Viewshed, Entity, &'static Faction, Option<&'static Grudges>, &'static CombatStats);
+
+/// Query data for describing a creature (or other named thing) on a tile
+pub type Described = (
+    Entity,
+    &'static Position,
+    &'static Name,
+    Option<&'static Health>,
+    Option<&'static AiState>,
+    Option<&'static Faction>,
+    Option<&'static Grudges>,
+    Option<&'static CombatStats>,
+    Has<Player>,
+);
+
+/// Short description of an entity for tooltips and look mode
+///
+/// Other creatures show how wounded they look and what they are doing
+/// rather than exact hit points, plus the player's chance to hit them when
+/// they are hostile.
+pub fn describe_entity(
+    name: &Name,
+    health: Option<&Health>,
+    state: Option<&AiState>,
+    is_player: bool,
+    to_hit: Option<u32>,
+) -> String {
+    let to_hit = to_hit.map(|chance| format!(", {}% to hit", chance)).unwrap_or_default();
+    match (health, is_player) {
+        (Some(health), true) => format!("You ({}/{} HP)", health.current, health.max),
+        (Some(health), false) => match state {
+            Some(state) => format!("{} ({}, {}{})", name.0, wound_description(health), state.label(), to_hit),
+            None => format!("{} ({}{})", name.0, wound_description(health), to_hit),
+        },
+        (None, _) => name.0.clone(),
+    }
+}
+
+/// Describe everything the player can see on `pos`
+pub fn describe_visible_entities(
+    pos: Position,
+    player: (&Viewshed, Entity, &Faction, Option<&Grudges>, &CombatStats),
+    factions: &FactionTable,
+    entity_query: &Query<Described, Without<Concealed>>,
+) -> Vec<String> {
+    let (viewshed, player, player_faction, player_grudges, player_stats) = player;
+    let me = Allegiance::new(player, player_faction, player_grudges);
+
+    entity_query
+        .iter()
+        .filter(|(_, entity_pos, ..)| **entity_pos == pos && viewshed.can_see(entity_pos))
+        .map(|(entity, _, name, health, state, faction, grudges, stats, is_player)| {
+            let hostile = faction.is_some_and(|faction| {
+                factions.is_hostile(me, Allegiance::new(entity, faction, grudges))
+            });
+            let to_hit = stats
+                .filter(|_| hostile)
+                .map(|stats| hit_distribution(player_stats, stats).chance_to_hit());
+            describe_entity(name, health, state, is_player, to_hit)
+        })
+        .collect()
+}
+
+/// Show the hovered tile's description next to the cursor
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn update_hover_tooltip_system(
+    hovered: Res<HoveredTile>,
+    map: Option<Res<CurrentMap>>,
+    visibility_map: Res<VisibilityMap>,
+    memory: Res<EntityMemory>,
+    factions: Res<FactionTable>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    player_query: Query<Onlooker, With<Player>>,
+    entity_query: Query<Described, Without<Concealed>>,
+    mut tooltip_query: Query<(&mut Node, &mut Text, &mut Visibility), With<HoverTooltip>>,
+) {
+    let Ok((mut node, mut text, mut visibility)) = tooltip_query.get_single_mut() else {
+        return;
+    };
+
+    let cursor = window_query.get_single().ok().and_then(|window| window.cursor_position());
+    let description = match (hovered.0, map, player_query.get_single()) {
+        (Some(pos), Some(map), Ok(player)) => {
+            let visible_things = describe_visible_entities(pos, player, &factions, &entity_query);
+            describe_tile(&map, &visibility_map, &memory, pos, &visible_things)
+        }
+        _ => None,
+    };
+
+    let (Some(lines), Some(cursor)) = (description, cursor) else {
+        if *visibility != Visibility::Hidden {
+            *visibility = Visibility::Hidden;
+        }
+        return;
+    };
+
+    let joined = lines.join("\n");
+    if text.0 != joined {
+        text.0 = joined;
+    }
+    let (left, top) = (Val::Px(cursor.x + 16.0), Val::Px(cursor.y + 16.0));
+    if node.left != left || node.top != top {
+        node.left = left;
+        node.top = top;
+    }
+    if *visibility != Visibility::Visible {
+        *visibility = Visibility::Visible;
+    }
+}
@@ -0,0 +1,28 @@
+//! Headless games - the game rules stepped without a window, driven by the bot
+
+use bevy::prelude::*;
+use rust_roguelike::{headless_app, TurnState};
+use rust_roguelike::components::Player;
+use rust_roguelike::resources::{ActionSource, CurrentMap};
+
+#[test]
+fn bot_plays_a_headless_game() {
+    let mut app = headless_app(ActionSource::Bot);
+    let mut last_turn = None;
+    let mut completed_turns = 0;
+
+    for _ in 0..300 {
+        app.update();
+
+        let turn = app.world().resource::<State<TurnState>>().get().clone();
+        if last_turn == Some(TurnState::EnemyTurn) && turn == TurnState::PlayerTurn {
+            completed_turns += 1;
+        }
+        last_turn = Some(turn);
+    }
+
+    let world = app.world_mut();
+    assert!(world.get_resource::<CurrentMap>().is_some(), "no map was generated");
+    assert_eq!(world.query_filtered::<(), With<Player>>().iter(world).count(), 1, "expected one player");
+    assert!(completed_turns >= 1, "the turn never came back round to the player");
+}
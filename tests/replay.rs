@@ -0,0 +1,100 @@
+//! Recorded games - a game recorded with its seed plays back move for move
+
+use bevy::prelude::*;
+use rust_roguelike::{headless_app, apply_action_source_args};
+use rust_roguelike::components::{Player, Enemy, Position, Health};
+use rust_roguelike::resources::{
+    ActionSource, ActionRecording, CurrentMap, DijkstraMap, VisibilityMap, VisibilityState, GameRng, Depth,
+};
+use rust_roguelike::systems::TravelPlan;
+
+const SEED: u64 = 7;
+const OPENING_FRAMES: usize = 30;
+const TRAVEL_FRAMES: usize = 60;
+const BOT_FRAMES: usize = 300;
+
+/// Where the player stands, their health, the depth and where the living monsters are
+fn snapshot(app: &mut App) -> (Position, i32, u32, Vec<(i32, i32)>) {
+    let world = app.world_mut();
+    let (pos, health) = world
+        .query_filtered::<(&Position, &Health), With<Player>>()
+        .single(world);
+    let (pos, health) = (*pos, health.current);
+    let mut monsters: Vec<(i32, i32)> = world
+        .query_filtered::<&Position, With<Enemy>>()
+        .iter(world)
+        .map(|pos| (pos.x, pos.y))
+        .collect();
+    monsters.sort();
+    (pos, health, world.resource::<Depth>().0, monsters)
+}
+
+fn player_position(app: &mut App) -> Position {
+    snapshot(app).0
+}
+
+/// Send the player to the furthest tile they know a way to, as a click would
+fn travel_to_furthest_known_tile(app: &mut App) {
+    let start = player_position(app);
+    let world = app.world_mut();
+    let monsters: Vec<Entity> = world.query_filtered::<Entity, With<Enemy>>().iter(world).collect();
+
+    let map = world.resource::<CurrentMap>();
+    let visibility = world.resource::<VisibilityMap>();
+    let known = |pos: Position| visibility.get(&pos) != VisibilityState::Unseen;
+    let from_start = DijkstraMap::build_with(map, &[start], known);
+    let destination = (0..map.height as i32)
+        .flat_map(|y| (0..map.width as i32).map(move |x| Position::new(x, y)))
+        .filter_map(|pos| from_start.get(pos).map(|cost| (cost, pos)))
+        .max_by_key(|(cost, _)| *cost)
+        .map(|(_, pos)| pos)
+        .expect("the player knows some tiles");
+    let path = DijkstraMap::build_with(map, &[destination], known)
+        .path_to_goal(map, start)
+        .expect("a way to the destination");
+
+    let mut travel = world.resource_mut::<TravelPlan>();
+    travel.path = path.into();
+    travel.seen_monsters = monsters.into_iter().collect();
+}
+
+#[test]
+fn recorded_game_replays_the_same_way() {
+    // Record: the bot plays, takes a click-to-travel trip, then plays on
+    let mut app = headless_app(ActionSource::Bot);
+    app.insert_resource(GameRng::new(SEED))
+        .insert_resource(ActionRecording::new("unused"));
+    for _ in 0..OPENING_FRAMES {
+        app.update();
+    }
+
+    app.insert_resource(ActionSource::Keyboard);
+    let start = player_position(&mut app);
+    travel_to_furthest_known_tile(&mut app);
+    for _ in 0..TRAVEL_FRAMES {
+        app.update();
+    }
+    assert_ne!(player_position(&mut app), start, "the player never travelled");
+
+    app.insert_resource(ActionSource::Bot);
+    for _ in 0..BOT_FRAMES {
+        app.update();
+    }
+    let recorded = snapshot(&mut app);
+    let text = app.world().resource::<ActionRecording>().to_text(SEED);
+
+    // Replay it through the command line arguments
+    let path = std::env::temp_dir().join(format!("roguelike-replay-{}.txt", std::process::id()));
+    std::fs::write(&path, text).unwrap();
+    let mut replay_app = headless_app(ActionSource::Keyboard);
+    let args = ["--replay".to_string(), path.to_string_lossy().into_owned()];
+    let applied = apply_action_source_args(&mut replay_app, args);
+    std::fs::remove_file(&path).ok();
+    applied.unwrap();
+
+    for _ in 0..OPENING_FRAMES + TRAVEL_FRAMES + BOT_FRAMES {
+        replay_app.update();
+    }
+
+    assert_eq!(snapshot(&mut replay_app), recorded);
+}